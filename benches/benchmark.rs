@@ -17,8 +17,7 @@
 // WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-use criterion::{criterion_group, criterion_main, Criterion};
-use std::u64;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use unsigned_varint::{decode, encode};
 
 fn bench_decode(c: &mut Criterion) {
@@ -29,6 +28,72 @@ fn bench_decode(c: &mut Criterion) {
     }));
 }
 
+// Pseudo-random values of uniformly distributed bit length, so that the
+// encoded lengths can not be predicted.
+fn random_sequence() -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut buf = encode::u64_buffer();
+    let mut x = 0x1234_5678_9abc_def1_u64;
+    for _ in 0 .. 0x10000 {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        bytes.extend_from_slice(encode::u64(x >> (x % 64), &mut buf))
+    }
+    bytes
+}
+
+fn bench_decode_sequence(c: &mut Criterion) {
+    let bytes = random_sequence();
+    c.bench_function("decode sequence", move |b| b.iter(|| {
+        let mut input = black_box(&bytes[..]);
+        while !input.is_empty() {
+            let (n, rest) = decode::u64(input).unwrap();
+            black_box(n);
+            input = rest
+        }
+    }));
+}
+
+fn bench_decode_slice(c: &mut Criterion) {
+    let bytes = random_sequence();
+    let mut out = vec![0u64; 0x10000];
+    let mut group = c.benchmark_group("decode slice");
+    group.bench_function("per element", |b| b.iter(|| {
+        let mut input = black_box(&bytes[..]);
+        for slot in out.iter_mut() {
+            let (n, rest) = decode::u64(input).unwrap();
+            *slot = n;
+            input = rest
+        }
+    }));
+    group.bench_function("batch", |b| b.iter(|| {
+        assert_eq!(0x10000, decode::u64_slice(black_box(&bytes), &mut out).unwrap().0)
+    }));
+    group.finish()
+}
+
+fn bench_scan_sequence(c: &mut Criterion) {
+    let bytes = random_sequence();
+    let mut group = c.benchmark_group("scan sequence");
+    group.bench_function("decode u128", |b| b.iter(|| {
+        let mut input = black_box(&bytes[..]);
+        while !input.is_empty() {
+            input = decode::u128(input).unwrap().1
+        }
+    }));
+    group.bench_function("skip", |b| b.iter(|| {
+        let mut input = black_box(&bytes[..]);
+        while !input.is_empty() {
+            input = decode::skip(input).unwrap()
+        }
+    }));
+    group.bench_function("count", |b| b.iter(|| {
+        assert_eq!(0x10000, decode::count(black_box(&bytes)))
+    }));
+    group.finish()
+}
+
 fn bench_encode(c: &mut Criterion) {
     let mut buf = [0; 10];
     let encoded = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 1];
@@ -37,6 +102,41 @@ fn bench_encode(c: &mut Criterion) {
     }));
 }
 
+fn bench_encode_distributions(c: &mut Criterion) {
+    // Pseudo-random values, so that the encoded lengths can not be predicted.
+    let mut x = 0x1234_5678_9abc_def1_u64;
+    let mut random = move || {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        x
+    };
+    let distributions: [(&str, Vec<u64>); 4] = [
+        ("1 byte", (0 .. 0x10000).map(|_| random() % 0x80).collect()),
+        ("2 bytes", (0 .. 0x10000).map(|_| 0x80 + random() % 0x3f80).collect()),
+        // Mostly short lengths with occasional large messages.
+        ("length prefixes", (0 .. 0x10000).map(|_| {
+            let r = random();
+            match r % 16 {
+                0 ..= 7 => r >> 57,
+                8 ..= 13 => r >> 50,
+                _ => r >> 40
+            }
+        }).collect()),
+        ("uniform bit length", (0 .. 0x10000).map(|_| { let r = random(); r >> (r % 64) }).collect())
+    ];
+    let mut group = c.benchmark_group("encode u64");
+    for (name, values) in distributions.iter() {
+        let mut buf = encode::u64_buffer();
+        group.bench_function(*name, |b| b.iter(|| {
+            for n in values {
+                black_box(encode::u64(*n, &mut buf));
+            }
+        }));
+    }
+    group.finish()
+}
+
 #[cfg(feature = "codec")]
 fn bench_codec(c: &mut Criterion) {
     use bytes::{Bytes, BytesMut};
@@ -54,10 +154,10 @@ fn bench_codec(c: &mut Criterion) {
 }
 
 #[cfg(feature = "codec")]
-criterion_group!(benches, bench_encode, bench_decode, bench_codec);
+criterion_group!(benches, bench_encode, bench_decode, bench_decode_sequence, bench_decode_slice, bench_scan_sequence, bench_encode_distributions, bench_codec);
 
 #[cfg(not(feature = "codec"))]
-criterion_group!(benches, bench_encode, bench_decode);
+criterion_group!(benches, bench_encode, bench_decode, bench_decode_sequence, bench_decode_slice, bench_scan_sequence, bench_encode_distributions);
 
 criterion_main!(benches);
 
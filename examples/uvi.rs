@@ -37,7 +37,7 @@ pub fn main() {
                 Ok(n) => {
                     let mut buf = encode::u128_buffer();
                     let bytes = encode::u128(n, &mut buf);
-                    println!("{}", hex::encode(&bytes))
+                    println!("{}", hex::encode(bytes))
                 }
                 Err(e) => {
                     println!("{}", e);
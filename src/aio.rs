@@ -19,40 +19,109 @@
 
 //! Decode using [`futures_io::AsyncRead`] types.
 
-use crate::{decode, encode, io::ReadError};
+use crate::{decode::{self, ErrorKind, Options}, encode::U64_LEN, io::{decode_read, ReadError}, Varint};
 use futures_io::AsyncRead;
 use futures_util::io::AsyncReadExt;
-use std::io;
+use std::{
+    io,
+    num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize}
+};
+
+/// Try to read and decode a [`Varint`] from the given `AsyncRead` type.
+pub async fn read<T: Varint, R: AsyncRead + Unpin>(reader: R) -> Result<T, ReadError> {
+    read_with(reader, Options::new()).await
+}
+
+/// Try to read and decode a [`Varint`] from the given `AsyncRead` type using the
+/// given options.
+///
+/// At most as many bytes as permitted by the options are read.
+pub async fn read_with<T: Varint, R: AsyncRead + Unpin>(reader: R, options: Options) -> Result<T, ReadError> {
+    let mut b = T::Buffer::default();
+    let b = b.as_mut();
+    let max = options.max_len(b.len());
+    let len = read_bytes(reader, &mut b[.. max], 0).await?;
+    decode_read(&b[.. len], |b| T::decode_with(b, options))
+}
+
+/// Try to read and decode a `u64` of at most `max` from the given
+/// `AsyncRead` type.
+///
+/// Values greater than `max` are rejected with
+/// [`decode::ErrorKind::OutOfRange`]. No more bytes than the encoding of
+/// `max` occupies are read, unless the bytes read so far could start a
+/// non-minimal encoding of a value in range, see [`decode::u64_bounded`].
+pub async fn read_u64_bounded<R: AsyncRead + Unpin>(reader: R, max: u64) -> Result<u64, ReadError> {
+    read_u64_bounded_with(reader, max, Options::new()).await
+}
+
+/// Try to read and decode a `u64` of at most `max` from the given
+/// `AsyncRead` type using the given options.
+///
+/// See [`decode::u64_bounded_with`] for details.
+pub async fn read_u64_bounded_with<R: AsyncRead + Unpin>(mut reader: R, max: u64, options: Options) -> Result<u64, ReadError> {
+    let mut b = [0; U64_LEN];
+    let len = read_bytes(&mut reader, &mut b[.. decode::bounded_len(max, options)], 0).await?;
+    match decode_read(&b[.. len], |b| decode::u64_bounded_with(b, max, options)) {
+        // The bytes so far may start a non-minimal encoding of a value in range.
+        Err(ReadError::Decode { error, .. }) if error.kind() == ErrorKind::Insufficient => {
+            let len = read_bytes(reader, &mut b[.. options.max_len(U64_LEN)], len).await?;
+            decode_read(&b[.. len], |b| decode::u64_bounded_with(b, max, options))
+        }
+        result => result
+    }
+}
+
+/// Read into `b` byte by byte, starting at `from`, until a last byte has been
+/// read or `b` is full.
+///
+/// Returns the number of bytes in `b` which have been read by this and any
+/// previous call.
+async fn read_bytes<R: AsyncRead + Unpin>(mut reader: R, b: &mut [u8], from: usize) -> Result<usize, ReadError> {
+    for i in from .. b.len() {
+        let n = reader.read(&mut b[i .. i + 1]).await.map_err(|e| ReadError::io(e, &b[.. i]))?;
+        if n == 0 {
+            return Err(ReadError::io(io::ErrorKind::UnexpectedEof.into(), &b[.. i]))
+        }
+        if decode::is_last(b[i]) {
+            return Ok(i + 1)
+        }
+    }
+    // Let the decoder report why `b.len()` bytes are not enough.
+    Ok(b.len())
+}
 
 macro_rules! gen {
-    ($($name:ident, $d:expr, $t:ident, $b:ident);*) => {
+    ($($name:ident, $d:expr, $t:ident);*) => {
         $(
             #[doc = " Try to read and decode a "]
             #[doc = $d]
             #[doc = " from the given `AsyncRead` type."]
-            pub async fn $name<R: AsyncRead + Unpin>(mut reader: R) -> Result<$t, ReadError> {
-                let mut b = encode::$b();
-                for i in 0 .. b.len() {
-                    let n = reader.read(&mut b[i .. i + 1]).await?;
-                    if n == 0 {
-                        return Err(ReadError::Io(io::ErrorKind::UnexpectedEof.into()))
-                    }
-                    if decode::is_last(b[i]) {
-                        return Ok(decode::$t(&b[..= i])?.0)
-                    }
-                }
-                Err(decode::Error::Overflow)?
+            pub async fn $name<R: AsyncRead + Unpin>(reader: R) -> Result<$t, ReadError> {
+                read(reader).await
             }
         )*
     }
 }
 
 gen! {
-    read_u8,    "`u8`",    u8,    u8_buffer;
-    read_u16,   "`u16`",   u16,   u16_buffer;
-    read_u32,   "`u32`",   u32,   u32_buffer;
-    read_u64,   "`u64`",   u64,   u64_buffer;
-    read_u128,  "`u128`",  u128,  u128_buffer;
-    read_usize, "`usize`", usize, usize_buffer
+    read_u8,    "`u8`",    u8;
+    read_u16,   "`u16`",   u16;
+    read_u32,   "`u32`",   u32;
+    read_u64,   "`u64`",   u64;
+    read_u128,  "`u128`",  u128;
+    read_usize, "`usize`", usize;
+    read_i8,    "`i8`",    i8;
+    read_i16,   "`i16`",   i16;
+    read_i32,   "`i32`",   i32;
+    read_i64,   "`i64`",   i64;
+    read_i128,  "`i128`",  i128;
+    read_isize, "`isize`", isize;
+    read_nonzero_u8,    "`NonZeroU8`",    NonZeroU8;
+    read_nonzero_u16,   "`NonZeroU16`",   NonZeroU16;
+    read_nonzero_u32,   "`NonZeroU32`",   NonZeroU32;
+    read_nonzero_u64,   "`NonZeroU64`",   NonZeroU64;
+    read_nonzero_u128,  "`NonZeroU128`",  NonZeroU128;
+    read_nonzero_usize, "`NonZeroUsize`", NonZeroUsize
 }
 
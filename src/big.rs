@@ -0,0 +1,375 @@
+// Copyright 2026 Parity Technologies (UK) Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS
+// OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Varints of arbitrary-width unsigned integers.
+//!
+//! Numbers are given as little-endian byte slices or as little-endian `u64`
+//! limbs. Decoding into a fixed-width output treats it like a target type of
+//! that width, e.g. values exceeding it are rejected according to the given
+//! [`Options`].
+//!
+//! Support for `num_bigint::BigUint` and `primitive_types::{U256, U512}` is
+//! available with the features `num-bigint` and `primitive-types`.
+
+use crate::{decode::{self, Error, ErrorKind, Options}, encode};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Little-endian sequence of bits.
+trait Bits {
+    /// The number of significant bits.
+    fn bit_len(&self) -> usize;
+
+    /// The 7-bit group starting at the given bit offset.
+    fn group(&self, offset: usize) -> u8;
+}
+
+/// Fixed-width little-endian sequence of bits.
+trait BitsMut {
+    /// The number of bits.
+    fn capacity(&self) -> usize;
+
+    /// Set all bits to zero.
+    fn clear(&mut self);
+
+    /// Set the bits of the 7-bit group starting at the given bit offset.
+    ///
+    /// Bits of `group` beyond the capacity must be zero.
+    fn set_group(&mut self, offset: usize, group: u8);
+}
+
+impl Bits for [u8] {
+    fn bit_len(&self) -> usize {
+        match self.iter().rposition(|b| *b != 0) {
+            Some(i) => i * 8 + 8 - self[i].leading_zeros() as usize,
+            None => 0
+        }
+    }
+
+    fn group(&self, offset: usize) -> u8 {
+        let (i, shift) = (offset / 8, offset % 8);
+        let lo = self.get(i).map_or(0, |b| b >> shift);
+        let hi = if shift > 1 { self.get(i + 1).map_or(0, |b| b << (8 - shift)) } else { 0 };
+        (lo | hi) & 0x7f
+    }
+}
+
+impl BitsMut for [u8] {
+    fn capacity(&self) -> usize {
+        self.len() * 8
+    }
+
+    fn clear(&mut self) {
+        self.fill(0)
+    }
+
+    fn set_group(&mut self, offset: usize, group: u8) {
+        let (i, shift) = (offset / 8, offset % 8);
+        let bits = u16::from(group) << shift;
+        self[i] |= bits as u8;
+        if let Some(b) = self.get_mut(i + 1) {
+            *b |= (bits >> 8) as u8
+        }
+    }
+}
+
+impl Bits for [u64] {
+    fn bit_len(&self) -> usize {
+        match self.iter().rposition(|l| *l != 0) {
+            Some(i) => i * 64 + 64 - self[i].leading_zeros() as usize,
+            None => 0
+        }
+    }
+
+    fn group(&self, offset: usize) -> u8 {
+        let (i, shift) = (offset / 64, offset % 64);
+        let lo = self.get(i).map_or(0, |l| l >> shift);
+        let hi = if shift > 57 { self.get(i + 1).map_or(0, |l| l << (64 - shift)) } else { 0 };
+        (lo | hi) as u8 & 0x7f
+    }
+}
+
+impl BitsMut for [u64] {
+    fn capacity(&self) -> usize {
+        self.len() * 64
+    }
+
+    fn clear(&mut self) {
+        self.fill(0)
+    }
+
+    fn set_group(&mut self, offset: usize, group: u8) {
+        let (i, shift) = (offset / 64, offset % 64);
+        self[i] |= u64::from(group) << shift;
+        if shift > 57 {
+            if let Some(l) = self.get_mut(i + 1) {
+                *l |= u64::from(group) >> (64 - shift)
+            }
+        }
+    }
+}
+
+/// The number of bytes a varint with the given number of significant bits occupies.
+const fn len_of_bits(bits: usize) -> usize {
+    if bits == 0 { 1 } else { bits.div_ceil(7) }
+}
+
+fn encode_bits<B: Bits + ?Sized>(number: &B, buf: &mut [u8]) -> Result<usize, encode::Error> {
+    let len = len_of_bits(number.bit_len());
+    if buf.len() < len {
+        return Err(encode::Error::BufferTooSmall { required: len })
+    }
+    for (i, b) in buf[.. len].iter_mut().enumerate() {
+        *b = number.group(i * 7) | 0x80
+    }
+    buf[len - 1] &= 0x7f;
+    Ok(len)
+}
+
+#[cfg(feature = "alloc")]
+fn encode_bits_vec<B: Bits + ?Sized>(number: &B, vec: &mut Vec<u8>) {
+    let start = vec.len();
+    vec.resize(start + len_of_bits(number.bit_len()), 0);
+    encode_bits(number, &mut vec[start ..]).expect("the buffer has the encoded length");
+}
+
+fn decode_bits<'a, B: BitsMut + ?Sized>(buf: &'a [u8], out: &mut B, options: Options) -> Result<&'a [u8], Error> {
+    out.clear();
+    let capacity = out.capacity();
+    let type_len = len_of_bits(capacity);
+    let max = options.max_len(type_len);
+    for (i, b) in buf.iter().enumerate() {
+        let shift = i * 7;
+        let mut k = b & 0x7f;
+        let room = capacity - shift;
+        if room < 7 {
+            if k >> room != 0 && options.reject_excess_bits() {
+                return Err(Error::new(ErrorKind::ExcessBits, i, i + 1))
+            }
+            k &= (1 << room) - 1
+        }
+        if k != 0 {
+            out.set_group(shift, k)
+        }
+        if decode::is_last(*b) {
+            if *b == 0 && i > 0 && options.rejects_padding(i + 1) {
+                return Err(Error::new(ErrorKind::NotMinimal, i, i + 1))
+            }
+            return Ok(&buf[i + 1 ..])
+        }
+        if i + 1 == max {
+            return Err(options.overflow(type_len, i))
+        }
+    }
+    Err(Error::insufficient(buf.len()))
+}
+
+/// Return the number of bytes the encoding of the given little-endian number occupies.
+pub fn encoded_len(number: &[u8]) -> usize {
+    len_of_bits(number.bit_len())
+}
+
+/// Return the number of bytes the encoding of the given little-endian limbs occupies.
+pub fn encoded_len_limbs(number: &[u64]) -> usize {
+    len_of_bits(number.bit_len())
+}
+
+/// Encode the given little-endian number into the given byte slice.
+///
+/// Returns the number of bytes written to the start of `buf` or
+/// [`encode::Error::BufferTooSmall`] if the encoding does not fit.
+pub fn encode(number: &[u8], buf: &mut [u8]) -> Result<usize, encode::Error> {
+    encode_bits(number, buf)
+}
+
+/// Encode the given little-endian limbs into the given byte slice.
+///
+/// Returns the number of bytes written to the start of `buf` or
+/// [`encode::Error::BufferTooSmall`] if the encoding does not fit.
+pub fn encode_limbs(number: &[u64], buf: &mut [u8]) -> Result<usize, encode::Error> {
+    encode_bits(number, buf)
+}
+
+/// Encode the given little-endian number and append it to the given `Vec`.
+///
+/// Only available when the feature `alloc` is present.
+#[cfg(feature = "alloc")]
+pub fn encode_vec(number: &[u8], vec: &mut Vec<u8>) {
+    encode_bits_vec(number, vec)
+}
+
+/// Encode the given little-endian limbs and append them to the given `Vec`.
+///
+/// Only available when the feature `alloc` is present.
+#[cfg(feature = "alloc")]
+pub fn encode_limbs_vec(number: &[u64], vec: &mut Vec<u8>) {
+    encode_bits_vec(number, vec)
+}
+
+/// Decode the given slice as little-endian number of the width of `out`.
+///
+/// Returns the remaining slice. On error the contents of `out` are unspecified.
+pub fn decode<'a>(buf: &'a [u8], out: &mut [u8]) -> Result<&'a [u8], Error> {
+    decode_with(buf, out, Options::new())
+}
+
+/// Decode the given slice as little-endian number of the width of `out`
+/// using the given options.
+///
+/// Returns the remaining slice. On error the contents of `out` are unspecified.
+pub fn decode_with<'a>(buf: &'a [u8], out: &mut [u8], options: Options) -> Result<&'a [u8], Error> {
+    decode_bits(buf, out, options)
+}
+
+/// Decode the given slice as `N` little-endian limbs.
+///
+/// Returns the limbs and the remaining slice.
+pub fn decode_limbs<const N: usize>(buf: &[u8]) -> Result<([u64; N], &[u8]), Error> {
+    decode_limbs_with(buf, Options::new())
+}
+
+/// Decode the given slice as `N` little-endian limbs using the given options.
+///
+/// Returns the limbs and the remaining slice.
+pub fn decode_limbs_with<const N: usize>(buf: &[u8], options: Options) -> Result<([u64; N], &[u8]), Error> {
+    let mut limbs = [0; N];
+    let rest = decode_bits(buf, &mut limbs[..], options)?;
+    Ok((limbs, rest))
+}
+
+/// Decode the given slice as little-endian number of any width, occupying
+/// at most `max_bytes` bytes.
+///
+/// Returns the number without trailing zero bytes and the remaining slice.
+/// The allocation is bounded by `max_bytes`, so untrusted input can not make
+/// it grow arbitrarily.
+///
+/// Only available when the feature `alloc` is present.
+#[cfg(feature = "alloc")]
+pub fn decode_vec(buf: &[u8], max_bytes: usize) -> Result<(Vec<u8>, &[u8]), Error> {
+    let options = Options::new().with_max_bytes(max_bytes);
+    let max = options.max_len(usize::MAX);
+    let len = match decode::encoded_len(&buf[.. buf.len().min(max)]) {
+        Some(len) => len,
+        None if buf.len() < max => return Err(Error::insufficient(buf.len())),
+        None => return Err(Error::new(ErrorKind::Overflow, max - 1, max))
+    };
+    let mut number = alloc::vec![0; (len * 7).div_ceil(8)];
+    let rest = decode_bits(buf, &mut number[..], options)?;
+    while number.last() == Some(&0) {
+        number.pop();
+    }
+    Ok((number, rest))
+}
+
+/// Encode the given `BigUint` and append it to the given `Vec`.
+///
+/// Only available when the feature `num-bigint` is present.
+#[cfg(feature = "num-bigint")]
+pub fn encode_biguint(number: &num_bigint::BigUint, vec: &mut Vec<u8>) {
+    encode_limbs_vec(&number.to_u64_digits(), vec)
+}
+
+/// Decode the given slice as `BigUint`, occupying at most `max_bytes` bytes.
+///
+/// Returns the value and the remaining slice. See [`decode_vec`] for details.
+///
+/// Only available when the feature `num-bigint` is present.
+#[cfg(feature = "num-bigint")]
+pub fn decode_biguint(buf: &[u8], max_bytes: usize) -> Result<(num_bigint::BigUint, &[u8]), Error> {
+    let (number, rest) = decode_vec(buf, max_bytes)?;
+    Ok((num_bigint::BigUint::from_bytes_le(&number), rest))
+}
+
+/// Maximum number of bytes an encoded `U256` occupies.
+///
+/// Only available when the feature `primitive-types` is present.
+#[cfg(feature = "primitive-types")]
+pub const U256_LEN: usize = 37;
+
+/// Maximum number of bytes an encoded `U512` occupies.
+///
+/// Only available when the feature `primitive-types` is present.
+#[cfg(feature = "primitive-types")]
+pub const U512_LEN: usize = 74;
+
+/// Encode the given `U256` into the given byte array.
+///
+/// Returns the slice of encoded bytes.
+///
+/// Only available when the feature `primitive-types` is present.
+#[cfg(feature = "primitive-types")]
+pub fn encode_u256(number: primitive_types::U256, buf: &mut [u8; U256_LEN]) -> &[u8] {
+    let len = encode_bits(&number.0[..], buf).expect("U256_LEN bytes fit every U256");
+    &buf[.. len]
+}
+
+/// Encode the given `U512` into the given byte array.
+///
+/// Returns the slice of encoded bytes.
+///
+/// Only available when the feature `primitive-types` is present.
+#[cfg(feature = "primitive-types")]
+pub fn encode_u512(number: primitive_types::U512, buf: &mut [u8; U512_LEN]) -> &[u8] {
+    let len = encode_bits(&number.0[..], buf).expect("U512_LEN bytes fit every U512");
+    &buf[.. len]
+}
+
+/// Decode the given slice as `U256`.
+///
+/// Returns the value and the remaining slice.
+///
+/// Only available when the feature `primitive-types` is present.
+#[cfg(feature = "primitive-types")]
+pub fn decode_u256(buf: &[u8]) -> Result<(primitive_types::U256, &[u8]), Error> {
+    decode_u256_with(buf, Options::new())
+}
+
+/// Decode the given slice as `U256` using the given options.
+///
+/// Returns the value and the remaining slice.
+///
+/// Only available when the feature `primitive-types` is present.
+#[cfg(feature = "primitive-types")]
+pub fn decode_u256_with(buf: &[u8], options: Options) -> Result<(primitive_types::U256, &[u8]), Error> {
+    let (limbs, rest) = decode_limbs_with(buf, options)?;
+    Ok((primitive_types::U256(limbs), rest))
+}
+
+/// Decode the given slice as `U512`.
+///
+/// Returns the value and the remaining slice.
+///
+/// Only available when the feature `primitive-types` is present.
+#[cfg(feature = "primitive-types")]
+pub fn decode_u512(buf: &[u8]) -> Result<(primitive_types::U512, &[u8]), Error> {
+    decode_u512_with(buf, Options::new())
+}
+
+/// Decode the given slice as `U512` using the given options.
+///
+/// Returns the value and the remaining slice.
+///
+/// Only available when the feature `primitive-types` is present.
+#[cfg(feature = "primitive-types")]
+pub fn decode_u512_with(buf: &[u8], options: Options) -> Result<(primitive_types::U512, &[u8]), Error> {
+    let (limbs, rest) = decode_limbs_with(buf, options)?;
+    Ok((primitive_types::U512(limbs), rest))
+}
@@ -0,0 +1,150 @@
+// Copyright 2026 Parity Technologies (UK) Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS
+// OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Extension traits to get and put varints from and to [`bytes`] buffers.
+//!
+//! Only available when the feature `bytes` is present.
+
+use bytes::{Buf, BufMut};
+use crate::{decode::{self, Options}, Varint};
+
+macro_rules! get {
+    ($($name:ident, $d:expr, $t:ident);*) => {
+        $(
+            #[doc = " Get a "]
+            #[doc = $d]
+            #[doc = " from this buffer.\n\n"]
+            #[doc = " See [`BufExt::get_uvarint`] for details."]
+            fn $name(&mut self) -> Result<$t, decode::Error> {
+                self.get_uvarint()
+            }
+        )*
+    }
+}
+
+macro_rules! put {
+    ($($name:ident, $d:expr, $t:ident);*) => {
+        $(
+            #[doc = " Put the given "]
+            #[doc = $d]
+            #[doc = " into this buffer.\n\n"]
+            #[doc = " See [`BufMutExt::put_uvarint`] for details."]
+            fn $name(&mut self, number: $t) {
+                self.put_uvarint(number)
+            }
+        )*
+    }
+}
+
+/// Extension trait to decode varints from a [`Buf`].
+pub trait BufExt: Buf {
+    /// Get a [`Varint`] from this buffer.
+    ///
+    /// The varint may span several chunks of the buffer. On success exactly
+    /// the bytes of the varint are consumed.
+    ///
+    /// # Partial consumption
+    ///
+    /// On error the bytes examined so far, as given by
+    /// [`decode::Error::examined`], have been consumed. In particular, if the
+    /// buffer ends before the varint does, all remaining bytes are consumed.
+    fn get_uvarint<T: Varint>(&mut self) -> Result<T, decode::Error> {
+        self.get_uvarint_with(Options::new())
+    }
+
+    /// Get a [`Varint`] from this buffer using the given options.
+    ///
+    /// See [`BufExt::get_uvarint`] for details.
+    fn get_uvarint_with<T: Varint>(&mut self, options: Options) -> Result<T, decode::Error> {
+        let chunk = self.chunk();
+        // Decode directly from the current chunk if it contains the varint.
+        if chunk.len() >= T::MAX_LEN || decode::encoded_len(chunk).is_some() {
+            let (result, consumed) = match T::decode_with(chunk, options) {
+                Ok((n, rest)) => (Ok(n), chunk.len() - rest.len()),
+                Err(e) => {
+                    let examined = e.examined();
+                    (Err(e), examined)
+                }
+            };
+            self.advance(consumed);
+            return result
+        }
+        // Otherwise collect the bytes of the varint across chunks.
+        let mut buf = T::Buffer::default();
+        let b = buf.as_mut();
+        let max = options.max_len(b.len());
+        let mut i = 0;
+        while i < max && self.has_remaining() {
+            b[i] = self.get_u8();
+            i += 1;
+            if decode::is_last(b[i - 1]) {
+                break
+            }
+        }
+        T::decode_with(&b[.. i], options).map(|(n, _)| n)
+    }
+
+    get! {
+        get_uvarint_u8,    "`u8`",    u8;
+        get_uvarint_u16,   "`u16`",   u16;
+        get_uvarint_u32,   "`u32`",   u32;
+        get_uvarint_u64,   "`u64`",   u64;
+        get_uvarint_u128,  "`u128`",  u128;
+        get_uvarint_usize, "`usize`", usize;
+        get_uvarint_i8,    "ZigZag-encoded `i8`",    i8;
+        get_uvarint_i16,   "ZigZag-encoded `i16`",   i16;
+        get_uvarint_i32,   "ZigZag-encoded `i32`",   i32;
+        get_uvarint_i64,   "ZigZag-encoded `i64`",   i64;
+        get_uvarint_i128,  "ZigZag-encoded `i128`",  i128;
+        get_uvarint_isize, "ZigZag-encoded `isize`", isize
+    }
+}
+
+impl<B: Buf + ?Sized> BufExt for B {}
+
+/// Extension trait to encode varints into a [`BufMut`].
+pub trait BufMutExt: BufMut {
+    /// Put the given [`Varint`] into this buffer.
+    ///
+    /// # Panics
+    ///
+    /// If there is not enough remaining capacity for the encoded bytes,
+    /// like [`BufMut::put_slice`].
+    fn put_uvarint<T: Varint>(&mut self, number: T) {
+        let mut buf = T::Buffer::default();
+        self.put_slice(number.encode_into(&mut buf))
+    }
+
+    put! {
+        put_uvarint_u8,    "`u8`",    u8;
+        put_uvarint_u16,   "`u16`",   u16;
+        put_uvarint_u32,   "`u32`",   u32;
+        put_uvarint_u64,   "`u64`",   u64;
+        put_uvarint_u128,  "`u128`",  u128;
+        put_uvarint_usize, "`usize`", usize;
+        put_uvarint_i8,    "ZigZag-encoded `i8`",    i8;
+        put_uvarint_i16,   "ZigZag-encoded `i16`",   i16;
+        put_uvarint_i32,   "ZigZag-encoded `i32`",   i32;
+        put_uvarint_i64,   "ZigZag-encoded `i64`",   i64;
+        put_uvarint_i128,  "ZigZag-encoded `i128`",  i128;
+        put_uvarint_isize, "ZigZag-encoded `isize`", isize
+    }
+}
+
+impl<B: BufMut + ?Sized> BufMutExt for B {}
@@ -20,82 +20,117 @@
 //! `Encoder`/`Decoder` implementations for tokio or asynchronous_codec.
 
 use bytes::{Buf, BufMut, Bytes, BytesMut};
-use crate::{encode, decode::{self, Error}};
-use std::{io, marker::PhantomData, usize};
+use crate::{buf::BufMutExt, decode::{self, ErrorKind, Options, Peek}, Varint};
+use std::{io, marker::PhantomData};
 
 /// Encoder/Decoder of unsigned-varint values
-#[derive(Default)]
-pub struct Uvi<T>(PhantomData<T>);
-
-macro_rules! encoder_decoder_impls {
-    ($typ:ident, $arr:ident) => {
-        impl Uvi<$typ> {
-            fn serialise(&mut self, item: $typ, dst: &mut BytesMut) {
-                let mut buf = encode::$arr();
-                dst.extend_from_slice(encode::$typ(item, &mut buf))
-            }
+pub struct Uvi<T> {
+    /// the options to use for decoding
+    options: Options,
+    /// number of bytes of an incomplete varint already scanned (for decoding only)
+    scanned: usize,
+    _ty: PhantomData<T>
+}
 
-            fn deserialise(&mut self, src: &mut BytesMut) -> Result<Option<$typ>, io::Error> {
-                let (number, consumed) =
-                    match decode::$typ(src.as_ref()) {
-                        Ok((n, rem)) => (n, src.len() - rem.len()),
-                        Err(Error::Insufficient) => return Ok(None),
-                        Err(e) => return Err(io::Error::new(io::ErrorKind::Other, e))
-                    };
-                src.advance(consumed);
-                Ok(Some(number))
-            }
+impl<T> Default for Uvi<T> {
+    fn default() -> Self {
+        Self {
+            options: Options::new(),
+            scanned: 0,
+            _ty: PhantomData
         }
+    }
+}
 
-        #[cfg(feature = "codec")]
-        impl tokio_util::codec::Encoder<$typ> for Uvi<$typ> {
-            type Error = io::Error;
+impl<T> Uvi<T> {
+    /// Set the options to use for decoding.
+    pub fn set_options(&mut self, val: Options) {
+        self.options = val;
+        self.scanned = 0
+    }
 
-            fn encode(&mut self, item: $typ, dst: &mut BytesMut) -> Result<(), Self::Error> {
-                self.serialise(item, dst);
-                Ok(())
-            }
-        }
+    /// Return the options to use for decoding.
+    pub fn options(&self) -> Options {
+        self.options
+    }
+}
 
-        #[cfg(feature = "codec")]
-        impl tokio_util::codec::Decoder for Uvi<$typ> {
-            type Item = $typ;
-            type Error = io::Error;
+impl<T: Varint> Uvi<T> {
+    fn serialise(&mut self, item: T, dst: &mut BytesMut) {
+        dst.put_uvarint(item)
+    }
 
-            fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-                self.deserialise(src)
+    fn deserialise(&mut self, src: &mut BytesMut) -> Result<Option<T>, io::Error> {
+        if self.scanned >= src.len() {
+            // No new bytes, or a different buffer than in the last poll.
+            self.scanned = 0
+        }
+        if self.scanned > 0 {
+            // Only look at the bytes which arrived since the last poll.
+            match decode::peek_len_from::<T>(src.as_ref(), self.options, self.scanned) {
+                Ok(Peek::Incomplete) => {
+                    self.scanned = src.len();
+                    return Ok(None)
+                }
+                // Errors are reported by the decoder below.
+                Ok(Peek::Complete(_)) | Err(_) => self.scanned = 0
             }
         }
-
-        #[cfg(feature = "asynchronous_codec")]
-        impl asynchronous_codec::Encoder for Uvi<$typ> {
-            type Item<'a> = $typ;
-            type Error = io::Error;
-
-            fn encode(&mut self, item: Self::Item<'_>, dst: &mut BytesMut) -> Result<(), Self::Error> {
-                self.serialise(item, dst);
-                Ok(())
+        match T::decode_with(src.as_ref(), self.options) {
+            Ok((number, rest)) => {
+                let consumed = src.len() - rest.len();
+                src.advance(consumed);
+                Ok(Some(number))
+            }
+            Err(e) if e.kind() == ErrorKind::Insufficient => {
+                self.scanned = src.len();
+                Ok(None)
             }
+            Err(e) => Err(io::Error::other(e))
         }
+    }
+}
 
-        #[cfg(feature = "asynchronous_codec")]
-        impl asynchronous_codec::Decoder for Uvi<$typ> {
-            type Item = $typ;
-            type Error = io::Error;
+#[cfg(feature = "codec")]
+impl<T: Varint> tokio_util::codec::Encoder<T> for Uvi<T> {
+    type Error = io::Error;
 
-            fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-                self.deserialise(src)
-            }
-        }
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.serialise(item, dst);
+        Ok(())
     }
 }
 
-encoder_decoder_impls!(u8, u8_buffer);
-encoder_decoder_impls!(u16, u16_buffer);
-encoder_decoder_impls!(u32, u32_buffer);
-encoder_decoder_impls!(u64, u64_buffer);
-encoder_decoder_impls!(u128, u128_buffer);
-encoder_decoder_impls!(usize, usize_buffer);
+#[cfg(feature = "codec")]
+impl<T: Varint> tokio_util::codec::Decoder for Uvi<T> {
+    type Item = T;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.deserialise(src)
+    }
+}
+
+#[cfg(feature = "asynchronous_codec")]
+impl<T: Varint> asynchronous_codec::Encoder for Uvi<T> {
+    type Item<'a> = T;
+    type Error = io::Error;
+
+    fn encode(&mut self, item: Self::Item<'_>, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        self.serialise(item, dst);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "asynchronous_codec")]
+impl<T: Varint> asynchronous_codec::Decoder for Uvi<T> {
+    type Item = T;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.deserialise(src)
+    }
+}
 
 /// Encoder/Decoder of unsigned-varint, length-prefixed bytes
 pub struct UviBytes<T = Bytes> {
@@ -120,6 +155,16 @@ impl<T> Default for UviBytes<T> {
 }
 
 impl<T> UviBytes<T> {
+    /// Set the options to use for decoding the length prefix.
+    pub fn set_options(&mut self, val: Options) {
+        self.varint_codec.set_options(val)
+    }
+
+    /// Return the options to use for decoding the length prefix.
+    pub fn options(&self) -> Options {
+        self.varint_codec.options()
+    }
+
     /// Limit the maximum allowed length of bytes.
     pub fn set_max_len(&mut self, val: usize) {
         self.max = val
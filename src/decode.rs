@@ -18,117 +18,827 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 //! Basic unsigned-varint decoding.
+//!
+//! All decoding functions are `const fn`s and can be used to decode constant
+//! input at compile time.
 
-use core::{self, fmt};
+use crate::{encode, varint::sealed::Sealed, Varint};
+use core::{
+    self,
+    fmt,
+    iter::FusedIterator,
+    marker::PhantomData,
+    num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize}
+};
 
-/// Possible decoding errors.
+/// A decoding error.
+///
+/// Besides the [`ErrorKind`], an error records where in the input it was
+/// detected and how many bytes were examined before giving up.
 ///
 /// **Note**: The `std` feature is required for the `std::error::Error` impl and the conversion to
 /// `std::io::Error`.
-#[non_exhaustive]
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub enum Error {
+pub struct Error {
+    kind: ErrorKind,
+    offset: usize,
+    examined: usize,
+    needed: Option<usize>
+}
+
+impl Error {
+    /// Create an error of the given kind, detected at `offset` after examining
+    /// `examined` bytes.
+    pub(crate) const fn new(kind: ErrorKind, offset: usize, examined: usize) -> Self {
+        Error { kind, offset, examined, needed: None }
+    }
+
+    /// Create an [`ErrorKind::Insufficient`] error for an input of the given
+    /// length which lacks a last byte.
+    pub(crate) const fn insufficient(len: usize) -> Self {
+        Error { kind: ErrorKind::Insufficient, offset: len, examined: len, needed: Some(1) }
+    }
+
+    /// Move the offset of this error by the given number of bytes.
+    pub(crate) const fn shift(mut self, by: usize) -> Self {
+        self.offset += by;
+        self
+    }
+
+    /// The kind of error.
+    pub const fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The offset into the input of the byte at which the error was detected.
+    ///
+    /// For [`ErrorKind::Insufficient`] this is the length of the input.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The number of bytes which have been examined before the error occurred.
+    pub const fn examined(&self) -> usize {
+        self.examined
+    }
+
+    /// The minimum number of additional input bytes required, if known.
+    ///
+    /// Only set for [`ErrorKind::Insufficient`].
+    pub const fn needed(&self) -> Option<usize> {
+        self.needed
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at offset {}", self.kind, self.offset)
+    }
+}
+
+/// Only available when the feature `std` is present.
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Only available when the feature `std` is present.
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    fn from(e: Error) -> Self {
+        let kind = match e.kind {
+            ErrorKind::Insufficient => std::io::ErrorKind::UnexpectedEof,
+            ErrorKind::Overflow => std::io::ErrorKind::InvalidData,
+            ErrorKind::NotMinimal => std::io::ErrorKind::InvalidData,
+            ErrorKind::ExcessBits => std::io::ErrorKind::InvalidData,
+            ErrorKind::ExceedsSpecLimit => std::io::ErrorKind::InvalidData,
+            ErrorKind::Zero => std::io::ErrorKind::InvalidData,
+            ErrorKind::OutOfRange => std::io::ErrorKind::InvalidData,
+            ErrorKind::Ambiguous => std::io::ErrorKind::InvalidData,
+        };
+        std::io::Error::new(kind, e)
+    }
+}
+
+/// Possible kinds of decoding errors.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
     /// Not enough input bytes.
     Insufficient,
     /// Input bytes exceed maximum.
     Overflow,
     /// Encoding is not minimal (has trailing zero bytes).
     NotMinimal,
+    /// Decoded value does not fit into the target type.
+    ExcessBits,
+    /// Input bytes exceed the maximum of the multiformats specification.
+    ExceedsSpecLimit,
+    /// Decoded value is zero but a non-zero value is required.
+    Zero,
+    /// Decoded value exceeds the given upper bound.
+    OutOfRange,
+    /// The start of a varint decoded backwards can not be determined.
+    Ambiguous,
 }
 
-impl fmt::Display for Error {
+impl fmt::Display for ErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Error::Insufficient => f.write_str("not enough input bytes"),
-            Error::Overflow => f.write_str("input bytes exceed maximum"),
-            Error::NotMinimal => f.write_str("encoding is not minimal"),
+            ErrorKind::Insufficient => f.write_str("not enough input bytes"),
+            ErrorKind::Overflow => f.write_str("input bytes exceed maximum"),
+            ErrorKind::NotMinimal => f.write_str("encoding is not minimal"),
+            ErrorKind::ExcessBits => f.write_str("value exceeds the target type"),
+            ErrorKind::ExceedsSpecLimit => f.write_str("input bytes exceed the specification limit"),
+            ErrorKind::Zero => f.write_str("value is zero"),
+            ErrorKind::OutOfRange => f.write_str("value exceeds the upper bound"),
+            ErrorKind::Ambiguous => f.write_str("start of varint is ambiguous"),
         }
     }
 }
 
-/// Only available when the feature `std` is present.
-#[cfg(feature = "std")]
-impl std::error::Error for Error {}
+/// Maximum number of bytes of a varint according to the multiformats
+/// unsigned-varint specification, i.e. at most 63 bits.
+pub const SPEC_MAX_LEN: usize = 9;
+
+/// Options to customise decoding.
+///
+/// The default options, as used by [`u64()`] etc., reject non-minimal
+/// encodings, accept as many bytes as required for the maximum value of the
+/// target type and reject bits which exceed the target type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Options {
+    require_minimal: bool,
+    max_bytes: Option<usize>,
+    reject_excess_bits: bool,
+    spec_limit: bool,
+    padded_len: usize
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options::new()
+    }
+}
+
+impl Options {
+    /// Create the default options.
+    pub const fn new() -> Self {
+        Options {
+            require_minimal: true,
+            max_bytes: None,
+            reject_excess_bits: true,
+            spec_limit: false,
+            padded_len: 0
+        }
+    }
+
+    /// Set whether non-minimal encodings are rejected with [`ErrorKind::NotMinimal`].
+    pub const fn with_require_minimal(mut self, val: bool) -> Self {
+        self.require_minimal = val;
+        self
+    }
+
+    /// Limit the number of bytes a varint may occupy.
+    ///
+    /// Longer varints are rejected with [`ErrorKind::Overflow`]. Limits greater
+    /// than the maximum length of the target type have no effect and at least
+    /// one byte is always accepted.
+    pub const fn with_max_bytes(mut self, val: usize) -> Self {
+        self.max_bytes = Some(val);
+        self
+    }
+
+    /// Set whether bits exceeding the target type are rejected with
+    /// [`ErrorKind::ExcessBits`] instead of being dropped.
+    pub const fn with_reject_excess_bits(mut self, val: bool) -> Self {
+        self.reject_excess_bits = val;
+        self
+    }
+
+    /// Set whether varints longer than [`SPEC_MAX_LEN`] bytes are rejected
+    /// with [`ErrorKind::ExceedsSpecLimit`], as required by the multiformats
+    /// unsigned-varint specification.
+    ///
+    /// A smaller limit set with [`Options::with_max_bytes`] takes precedence.
+    pub const fn with_spec_limit(mut self, val: bool) -> Self {
+        self.spec_limit = val;
+        self
+    }
+
+    /// Accept non-minimal encodings which occupy at most `width` bytes, e.g.
+    /// as written by [`encode::u64_padded`].
+    ///
+    /// Longer non-minimal encodings are still rejected if minimal encodings
+    /// are required. The padding counts towards the maximum length of the
+    /// target type.
+    pub const fn with_padded_len(mut self, width: usize) -> Self {
+        self.padded_len = width;
+        self
+    }
+
+    /// Are non-minimal encodings rejected?
+    pub const fn require_minimal(&self) -> bool {
+        self.require_minimal
+    }
+
+    /// The maximum number of bytes a varint may occupy, if limited.
+    pub const fn max_bytes(&self) -> Option<usize> {
+        self.max_bytes
+    }
+
+    /// Are bits exceeding the target type rejected?
+    pub const fn reject_excess_bits(&self) -> bool {
+        self.reject_excess_bits
+    }
+
+    /// Are varints beyond the limit of the specification rejected?
+    pub const fn spec_limit(&self) -> bool {
+        self.spec_limit
+    }
+
+    /// The number of bytes up to which non-minimal encodings are accepted.
+    pub const fn padded_len(&self) -> usize {
+        self.padded_len
+    }
+
+    /// Is a non-minimal encoding of the given length rejected?
+    pub(crate) const fn rejects_padding(&self, len: usize) -> bool {
+        self.require_minimal && len > self.padded_len
+    }
+
+    /// The maximum number of bytes accepted for a type of the given maximum length.
+    pub(crate) const fn max_len(&self, type_len: usize) -> usize {
+        let len = self.type_max_len(type_len);
+        if self.spec_limit && len > SPEC_MAX_LEN {
+            SPEC_MAX_LEN
+        } else {
+            len
+        }
+    }
+
+    /// Like `max_len` but without the limit of the specification.
+    const fn type_max_len(&self, type_len: usize) -> usize {
+        match self.max_bytes {
+            Some(0) => 1,
+            Some(n) if n < type_len => n,
+            _ => type_len
+        }
+    }
+
+    /// The error for a varint exceeding `max_len` at the given offset.
+    pub(crate) const fn overflow(&self, type_len: usize, offset: usize) -> Error {
+        let kind =
+            if self.spec_limit && self.type_max_len(type_len) > SPEC_MAX_LEN {
+                ErrorKind::ExceedsSpecLimit
+            } else {
+                ErrorKind::Overflow
+            };
+        Error::new(kind, offset, offset + 1)
+    }
+}
+
+/// Error of the batch decoding functions, e.g. [`u32_slice`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SliceError {
+    decoded: usize,
+    offset: usize,
+    error: Error
+}
+
+impl SliceError {
+    /// The number of values which have been decoded before the error occurred.
+    pub fn decoded(&self) -> usize {
+        self.decoded
+    }
+
+    /// The offset into the input of the varint which failed to decode.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The error which occurred.
+    ///
+    /// Its offset is relative to the start of the whole input.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+}
+
+impl fmt::Display for SliceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "value {} at offset {}: {}", self.decoded, self.offset, self.error)
+    }
+}
 
 /// Only available when the feature `std` is present.
 #[cfg(feature = "std")]
-impl Into<std::io::Error> for Error {
-    fn into(self) -> std::io::Error {
-        let kind = match self {
-            Error::Insufficient => std::io::ErrorKind::UnexpectedEof,
-            Error::Overflow => std::io::ErrorKind::InvalidData,
-            Error::NotMinimal => std::io::ErrorKind::InvalidData,
-        };
-        std::io::Error::new(kind, self)
+impl std::error::Error for SliceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
     }
 }
 
 macro_rules! decode {
-    ($buf:expr, $max_bytes:expr, $typ:ident) => {{
-        let mut n = 0;
-        for (i, b) in $buf.iter().cloned().enumerate() {
-            let k = $typ::from(b & 0x7F);
-            n |= k << (i * 7);
+    ($buf:expr, $options:expr, $max_bytes:expr, $typ:ident) => {
+        decode!($buf, $options, $max_bytes, $typ, 0, 0)
+    };
+    ($buf:expr, $options:expr, $max_bytes:expr, $typ:ident, $n:expr, $i:expr) => {{
+        let max = $options.max_len($max_bytes + 1) - 1;
+        let mut n = $n;
+        let mut i = $i;
+        while i < $buf.len() {
+            let b = $buf[i];
+            let k = (b & 0x7F) as $typ;
+            let shift = i * 7;
+            if $options.reject_excess_bits
+                && shift + 7 > $typ::BITS as usize
+                && k >> ($typ::BITS as usize - shift) != 0
+            {
+                return Err(Error::new(ErrorKind::ExcessBits, i, i + 1));
+            }
+            n |= k << shift;
             if is_last(b) {
-                if b == 0 && i > 0 {
+                if b == 0 && i > 0 && $options.rejects_padding(i + 1) {
                     // If last byte (of a multi-byte varint) is zero, it could have been "more
                     // minimally" encoded by dropping that trailing zero.
-                    return Err(Error::NotMinimal);
+                    return Err(Error::new(ErrorKind::NotMinimal, i, i + 1));
                 }
-                return Ok((n, &$buf[i + 1..]));
+                return Ok((n, $buf.split_at(i + 1).1));
             }
-            if i == $max_bytes {
-                return Err(Error::Overflow);
+            if i == max {
+                return Err($options.overflow($max_bytes + 1, i));
             }
+            i += 1
         }
-        Err(Error::Insufficient)
+        Err(Error::insufficient($buf.len()))
     }};
 }
 
+// Like `decode!` but processes the first 8 bytes at once if available.
+macro_rules! decode_fast {
+    ($buf:expr, $options:expr, $max_bytes:expr, $typ:ident) => {{
+        if !$buf.is_empty() && is_last($buf[0]) {
+            return Ok(($buf[0] as $typ, $buf.split_at(1).1));
+        }
+        if $buf.len() >= 8 {
+            let max = $options.max_len($max_bytes + 1);
+            match decode_word($buf) {
+                (n, Some(len)) if len <= max => {
+                    if $options.reject_excess_bits && n > $typ::MAX as u64 {
+                        return Err(Error::new(ErrorKind::ExcessBits, len - 1, len));
+                    }
+                    if $buf[len - 1] == 0 && $options.rejects_padding(len) {
+                        return Err(Error::new(ErrorKind::NotMinimal, len - 1, len));
+                    }
+                    return Ok((n as $typ, $buf.split_at(len).1));
+                }
+                (n, None) if max > 8 => return decode!($buf, $options, $max_bytes, $typ, n as $typ, 8),
+                // The varint is too long; let the byte-wise decoder report the error.
+                _ => {}
+            }
+        }
+        decode!($buf, $options, $max_bytes, $typ)
+    }};
+}
+
+macro_rules! unzigzag {
+    ($number:expr, $signed:ident) => {{
+        let n = $number;
+        ((n >> 1) as $signed) ^ -((n & 1) as $signed)
+    }}
+}
+
+/// Decode the first 8 bytes of `buf` all at once.
+///
+/// Returns the value of the 7-bit groups up to and including the first last
+/// byte together with the encoded length. If none of the 8 bytes is a last
+/// byte, the value of all 8 groups is returned without a length.
+///
+/// `buf` must be at least 8 bytes long.
+#[inline]
+const fn decode_word(buf: &[u8]) -> (u64, Option<usize>) {
+    let word = u64::from_le_bytes([buf[0], buf[1], buf[2], buf[3], buf[4], buf[5], buf[6], buf[7]]);
+    // The lowest set bit marks the MSB of the last byte.
+    let stops = !word & 0x8080_8080_8080_8080;
+    let (x, len) =
+        if stops == 0 {
+            (word & 0x7f7f_7f7f_7f7f_7f7f, None)
+        } else {
+            // Keep the 7-bit groups up to and including the last byte.
+            let len = (stops.trailing_zeros() / 8 + 1) as usize;
+            (word & (stops ^ (stops - 1)) & 0x7f7f_7f7f_7f7f_7f7f, Some(len))
+        };
+    // Pack the 7-bit groups together.
+    let x = (x & 0x007f_007f_007f_007f) | ((x & 0x7f00_7f00_7f00_7f00) >> 1);
+    let x = (x & 0x0000_3fff_0000_3fff) | ((x & 0x3fff_0000_3fff_0000) >> 2);
+    let x = (x & 0x0000_0000_0fff_ffff) | ((x & 0x0fff_ffff_0000_0000) >> 4);
+    (x, len)
+}
+
 /// Is this the last byte of an unsigned varint?
 #[inline]
-pub fn is_last(b: u8) -> bool {
+pub const fn is_last(b: u8) -> bool {
     b & 0x80 == 0
 }
 
+/// Return the length of the varint at the start of the given slice.
+///
+/// Only the continuation bits are inspected, i.e. the varint is not checked
+/// for validity. Returns `None` if the slice does not contain a last byte.
+#[inline]
+pub const fn encoded_len(buf: &[u8]) -> Option<usize> {
+    let mut i = 0;
+    while i < buf.len() {
+        if is_last(buf[i]) {
+            return Some(i + 1)
+        }
+        i += 1
+    }
+    None
+}
+
+/// The status of a possibly incomplete varint, as returned by [`peek_len`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Peek {
+    /// The varint is complete and occupies the given number of bytes.
+    Complete(usize),
+    /// The varint is incomplete, at least one more byte is needed.
+    Incomplete
+}
+
+/// Return the length of the varint of type `T` at the start of the given
+/// slice, or whether more input is needed to determine it.
+///
+/// Only the continuation bits are inspected if the varint is complete, i.e.
+/// its validity is checked by the subsequent decoding. If the slice contains
+/// as many bytes as permitted for `T` without a last byte, the error of the
+/// decoder is returned.
+#[inline]
+pub const fn peek_len<T: Varint>(buf: &[u8]) -> Result<Peek, Error> {
+    peek_len_with::<T>(buf, Options::new())
+}
+
+/// Like [`peek_len`] but using the given options to limit the length.
+#[inline]
+pub const fn peek_len_with<T: Varint>(buf: &[u8], options: Options) -> Result<Peek, Error> {
+    peek_len_from::<T>(buf, options, 0)
+}
+
+/// Like [`peek_len_with`] but starting the search for the last byte at `from`.
+///
+/// The first `from` bytes should be known to be continuation bytes, e.g. from
+/// a previous call which returned [`Peek::Incomplete`] for a prefix of `buf`.
+/// Otherwise the result is still that of decoding `buf` from its start.
+#[inline]
+pub(crate) const fn peek_len_from<T: Varint>(buf: &[u8], options: Options, from: usize) -> Result<Peek, Error> {
+    let max = options.max_len(T::MAX_LEN);
+    let mut i = from;
+    while i < buf.len() && i < max {
+        if is_last(buf[i]) {
+            return Ok(Peek::Complete(i + 1))
+        }
+        i += 1
+    }
+    if i < max {
+        return Ok(Peek::Incomplete)
+    }
+    match check(buf, options, <T as Sealed>::BITS, <T as Sealed>::NONZERO) {
+        Err(e) => Err(e),
+        // A last byte within the first `from` bytes.
+        Ok(rest) => Ok(Peek::Complete(buf.len() - rest.len()))
+    }
+}
+
+/// Return the number of complete varints in the given slice.
+///
+/// Like [`encoded_len`] only the continuation bits are inspected, i.e. the
+/// varints are not checked for validity. An incomplete varint at the end of
+/// the slice is not counted.
+#[inline]
+pub const fn count(buf: &[u8]) -> usize {
+    let mut n = 0;
+    let mut i = 0;
+    // Count the last bytes eight at a time.
+    while i + 8 <= buf.len() {
+        let word = u64::from_le_bytes([
+            buf[i], buf[i + 1], buf[i + 2], buf[i + 3], buf[i + 4], buf[i + 5], buf[i + 6], buf[i + 7]
+        ]);
+        n += (!word & 0x8080_8080_8080_8080).count_ones() as usize;
+        i += 8
+    }
+    while i < buf.len() {
+        if is_last(buf[i]) {
+            n += 1
+        }
+        i += 1
+    }
+    n
+}
+
+/// Check the varint at the start of `buf` against the rules of a type with
+/// the given number of bits without computing its value.
+const fn check(buf: &[u8], options: Options, bits: u32, nonzero: bool) -> Result<&[u8], Error> {
+    let bits = bits as usize;
+    let type_len = bits.div_ceil(7);
+    let max = options.max_len(type_len);
+    if buf.len() >= 8 {
+        match decode_word(buf) {
+            // No excess bits are possible, so the varint is fully described
+            // by its value and the last byte.
+            (n, Some(len)) if len <= max && (len * 7 <= bits || n >> bits == 0) => {
+                if buf[len - 1] == 0 && len > 1 && options.rejects_padding(len) {
+                    return Err(Error::new(ErrorKind::NotMinimal, len - 1, len))
+                }
+                if n == 0 && nonzero {
+                    return Err(Error::new(ErrorKind::Zero, len - 1, len))
+                }
+                return Ok(buf.split_at(len).1)
+            }
+            // Let the byte-wise check report the error.
+            _ => {}
+        }
+    }
+    let mut zero = true;
+    let mut i = 0;
+    while i < buf.len() {
+        let b = buf[i];
+        let shift = i * 7;
+        let mut k = b & 0x7f;
+        if shift + 7 > bits {
+            if options.reject_excess_bits && k >> (bits - shift) != 0 {
+                return Err(Error::new(ErrorKind::ExcessBits, i, i + 1))
+            }
+            // Excess bits are dropped by the decoder.
+            k &= (1 << (bits - shift)) - 1
+        }
+        zero = zero && k == 0;
+        if is_last(b) {
+            if b == 0 && i > 0 && options.rejects_padding(i + 1) {
+                return Err(Error::new(ErrorKind::NotMinimal, i, i + 1))
+            }
+            if zero && nonzero {
+                return Err(Error::new(ErrorKind::Zero, i, i + 1))
+            }
+            return Ok(buf.split_at(i + 1).1)
+        }
+        if i + 1 == max {
+            return Err(options.overflow(type_len, i))
+        }
+        i += 1
+    }
+    Err(Error::insufficient(buf.len()))
+}
+
+/// Skip the varint at the start of the given slice.
+///
+/// The varint is checked like [`u128()`] does, but its value is not computed.
+/// Returns the remaining slice.
+#[inline]
+pub const fn skip(buf: &[u8]) -> Result<&[u8], Error> {
+    skip_with(buf, Options::new())
+}
+
+/// Skip the varint at the start of the given slice using the given options.
+///
+/// The varint is checked like [`u128_with`] does, but its value is not
+/// computed. Returns the remaining slice.
+#[inline]
+pub const fn skip_with(buf: &[u8], options: Options) -> Result<&[u8], Error> {
+    check(buf, options, u128::BITS, false)
+}
+
+/// Validate the varint at the start of the given slice as value of type `T`.
+///
+/// The varint is checked like the decoder of `T` does, but its value is not
+/// computed. Returns the remaining slice.
+#[inline]
+pub const fn validate<T: Varint>(buf: &[u8]) -> Result<&[u8], Error> {
+    validate_with::<T>(buf, Options::new())
+}
+
+/// Validate the varint at the start of the given slice as value of type `T`
+/// using the given options.
+///
+/// The varint is checked like the decoder of `T` does, but its value is not
+/// computed. Returns the remaining slice.
+#[inline]
+pub const fn validate_with<T: Varint>(buf: &[u8], options: Options) -> Result<&[u8], Error> {
+    check(buf, options, <T as Sealed>::BITS, <T as Sealed>::NONZERO)
+}
+
 /// Decode the given slice as `u8`.
 ///
 /// Returns the value and the remaining slice.
 #[inline]
-pub fn u8(buf: &[u8]) -> Result<(u8, &[u8]), Error> {
-    decode!(buf, 1, u8)
+pub const fn u8(buf: &[u8]) -> Result<(u8, &[u8]), Error> {
+    u8_with(buf, Options::new())
+}
+
+/// Decode the given slice as `u8` using the given options.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn u8_with(buf: &[u8], options: Options) -> Result<(u8, &[u8]), Error> {
+    decode!(buf, options, 1, u8)
 }
 
 /// Decode the given slice as `u16`.
 ///
 /// Returns the value and the remaining slice.
 #[inline]
-pub fn u16(buf: &[u8]) -> Result<(u16, &[u8]), Error> {
-    decode!(buf, 2, u16)
+pub const fn u16(buf: &[u8]) -> Result<(u16, &[u8]), Error> {
+    u16_with(buf, Options::new())
+}
+
+/// Decode the given slice as `u16` using the given options.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn u16_with(buf: &[u8], options: Options) -> Result<(u16, &[u8]), Error> {
+    decode!(buf, options, 2, u16)
 }
 
 /// Decode the given slice as `u32`.
 ///
 /// Returns the value and the remaining slice.
 #[inline]
-pub fn u32(buf: &[u8]) -> Result<(u32, &[u8]), Error> {
-    decode!(buf, 4, u32)
+pub const fn u32(buf: &[u8]) -> Result<(u32, &[u8]), Error> {
+    u32_with(buf, Options::new())
+}
+
+/// Decode the given slice as `u32` using the given options.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn u32_with(buf: &[u8], options: Options) -> Result<(u32, &[u8]), Error> {
+    decode_fast!(buf, options, 4, u32)
 }
 
 /// Decode the given slice as `u64`.
 ///
 /// Returns the value and the remaining slice.
 #[inline]
-pub fn u64(buf: &[u8]) -> Result<(u64, &[u8]), Error> {
-    decode!(buf, 9, u64)
+pub const fn u64(buf: &[u8]) -> Result<(u64, &[u8]), Error> {
+    u64_with(buf, Options::new())
+}
+
+/// Decode the given slice as `u64` using the given options.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn u64_with(buf: &[u8], options: Options) -> Result<(u64, &[u8]), Error> {
+    decode_fast!(buf, options, 9, u64)
+}
+
+/// Decode the given slice as `u64` according to the multiformats
+/// specification, i.e. with at most [`SPEC_MAX_LEN`] bytes.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn u64_spec(buf: &[u8]) -> Result<(u64, &[u8]), Error> {
+    u64_with(buf, Options::new().with_spec_limit(true))
+}
+
+/// The maximum number of bytes a `u64` of at most `max` may occupy with the
+/// given options.
+pub(crate) const fn bounded_len(max: u64, options: Options) -> usize {
+    let len =
+        if !options.require_minimal {
+            encode::U64_LEN
+        } else if options.padded_len > encode::u64_len(max) {
+            options.padded_len
+        } else {
+            encode::u64_len(max)
+        };
+    let type_len = options.max_len(encode::U64_LEN);
+    if len < type_len { len } else { type_len }
+}
+
+/// Decode the given slice as `u64` of at most `max`.
+///
+/// Values greater than `max` are rejected with [`ErrorKind::OutOfRange`]. No
+/// more bytes than the encoding of `max` occupies are examined, unless these
+/// bytes are in range on their own and could start a non-minimal encoding of
+/// a value in range, which is rejected with [`ErrorKind::NotMinimal`].
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn u64_bounded(buf: &[u8], max: u64) -> Result<(u64, &[u8]), Error> {
+    u64_bounded_with(buf, max, Options::new())
+}
+
+/// Decode the given slice as `u64` of at most `max` using the given options.
+///
+/// Values greater than `max` are rejected with [`ErrorKind::OutOfRange`]. No
+/// more bytes than the encoding of `max` occupies are examined, unless the
+/// options permit non-minimal encodings or these bytes are in range on their
+/// own. A longer encoding is then decoded further to tell a value out of
+/// range from a non-minimal encoding of a value in range, which is rejected
+/// with [`ErrorKind::NotMinimal`].
+///
+/// Returns the value and the remaining slice.
+pub const fn u64_bounded_with(buf: &[u8], max: u64, options: Options) -> Result<(u64, &[u8]), Error> {
+    let len = bounded_len(max, options);
+    let limited = len < options.max_len(encode::U64_LEN);
+    let bounded = if limited { options.with_max_bytes(len) } else { options };
+    match u64_with(buf, bounded) {
+        Ok((n, rest)) if n > max => {
+            let len = buf.len() - rest.len();
+            Err(Error::new(ErrorKind::OutOfRange, len - 1, len))
+        }
+        // Longer encodings are either out of range or not minimal, which can
+        // only be told apart early if the bytes so far are out of range.
+        Err(e) if limited && matches!(e.kind, ErrorKind::Overflow) => {
+            let mut low = 0;
+            let mut i = 0;
+            while i < len {
+                low |= ((buf[i] & 0x7f) as u64) << (i * 7);
+                i += 1
+            }
+            if low > max {
+                return Err(Error::new(ErrorKind::OutOfRange, e.offset, e.examined))
+            }
+            match u64_with(buf, options) {
+                Ok((_, rest)) => {
+                    let len = buf.len() - rest.len();
+                    Err(Error::new(ErrorKind::OutOfRange, len - 1, len))
+                }
+                Err(e) if matches!(e.kind, ErrorKind::Overflow | ErrorKind::ExcessBits) => {
+                    Err(Error::new(ErrorKind::OutOfRange, e.offset, e.examined))
+                }
+                Err(e) => Err(e)
+            }
+        }
+        other => other
+    }
+}
+
+/// Decode the varint at the end of the given slice as `u64`, scanning
+/// backwards from its last byte.
+///
+/// The last byte of `buf` must be the last byte of the varint. Its start is
+/// the byte after the nearest preceding byte whose continuation bit is not
+/// set, i.e. the varint must either start `buf` or follow the last byte of
+/// another varint. Any other byte with the continuation bit set in front of
+/// the varint would be taken as part of it. If no start is found within
+/// [`U64_LEN`](encode::U64_LEN) bytes, the input is rejected with
+/// [`ErrorKind::Ambiguous`].
+///
+/// The varint is then checked like with [`u64()`]. Returns the value and the
+/// slice in front of it.
+#[inline]
+pub const fn u64_rev(buf: &[u8]) -> Result<(u64, &[u8]), Error> {
+    u64_rev_with(buf, Options::new())
+}
+
+/// Decode the varint at the end of the given slice as `u64` using the given
+/// options, scanning backwards from its last byte.
+///
+/// See [`u64_rev`] for the rules to find the start of the varint, which is
+/// searched for within as many bytes as permitted by the options.
+pub const fn u64_rev_with(buf: &[u8], options: Options) -> Result<(u64, &[u8]), Error> {
+    let end = buf.len();
+    if end == 0 || !is_last(buf[end - 1]) {
+        return Err(Error::insufficient(end))
+    }
+    let max = options.max_len(encode::U64_LEN);
+    let mut start = end - 1;
+    while start > 0 && !is_last(buf[start - 1]) {
+        if end - start == max {
+            // The varint would start more than `max` bytes before its end.
+            return Err(Error::new(ErrorKind::Ambiguous, start - 1, end - start + 1))
+        }
+        start -= 1
+    }
+    let (prefix, varint) = buf.split_at(start);
+    match u64_with(varint, options) {
+        Ok((n, _)) => Ok((n, prefix)),
+        Err(e) => Err(e.shift(start))
+    }
 }
 
 /// Decode the given slice as `u128`.
 ///
 /// Returns the value and the remaining slice.
 #[inline]
-pub fn u128(buf: &[u8]) -> Result<(u128, &[u8]), Error> {
-    decode!(buf, 18, u128)
+pub const fn u128(buf: &[u8]) -> Result<(u128, &[u8]), Error> {
+    u128_with(buf, Options::new())
+}
+
+/// Decode the given slice as `u128` using the given options.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn u128_with(buf: &[u8], options: Options) -> Result<(u128, &[u8]), Error> {
+    decode_fast!(buf, options, 18, u128)
+}
+
+/// Decode the given slice as `u128` according to the multiformats
+/// specification, i.e. with at most [`SPEC_MAX_LEN`] bytes.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn u128_spec(buf: &[u8]) -> Result<(u128, &[u8]), Error> {
+    u128_with(buf, Options::new().with_spec_limit(true))
 }
 
 /// Decode the given slice as `usize`.
@@ -136,8 +846,20 @@ pub fn u128(buf: &[u8]) -> Result<(u128, &[u8]), Error> {
 /// Returns the value and the remaining slice.
 #[inline]
 #[cfg(target_pointer_width = "64")]
-pub fn usize(buf: &[u8]) -> Result<(usize, &[u8]), Error> {
-    u64(buf).map(|(n, i)| (n as usize, i))
+pub const fn usize(buf: &[u8]) -> Result<(usize, &[u8]), Error> {
+    usize_with(buf, Options::new())
+}
+
+/// Decode the given slice as `usize` using the given options.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+#[cfg(target_pointer_width = "64")]
+pub const fn usize_with(buf: &[u8], options: Options) -> Result<(usize, &[u8]), Error> {
+    match u64_with(buf, options) {
+        Ok((n, i)) => Ok((n as usize, i)),
+        Err(e) => Err(e)
+    }
 }
 
 /// Decode the given slice as `usize`.
@@ -145,6 +867,404 @@ pub fn usize(buf: &[u8]) -> Result<(usize, &[u8]), Error> {
 /// Returns the value and the remaining slice.
 #[inline]
 #[cfg(target_pointer_width = "32")]
-pub fn usize(buf: &[u8]) -> Result<(usize, &[u8]), Error> {
-    u32(buf).map(|(n, i)| (n as usize, i))
+pub const fn usize(buf: &[u8]) -> Result<(usize, &[u8]), Error> {
+    usize_with(buf, Options::new())
+}
+
+/// Decode the given slice as `usize` using the given options.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+#[cfg(target_pointer_width = "32")]
+pub const fn usize_with(buf: &[u8], options: Options) -> Result<(usize, &[u8]), Error> {
+    match u32_with(buf, options) {
+        Ok((n, i)) => Ok((n as usize, i)),
+        Err(e) => Err(e)
+    }
+}
+
+/// Decode the given slice as `usize` according to the multiformats
+/// specification, i.e. with at most [`SPEC_MAX_LEN`] bytes.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn usize_spec(buf: &[u8]) -> Result<(usize, &[u8]), Error> {
+    usize_with(buf, Options::new().with_spec_limit(true))
+}
+
+/// Decode the given slice as ZigZag-encoded `i8`.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn i8(buf: &[u8]) -> Result<(i8, &[u8]), Error> {
+    i8_with(buf, Options::new())
+}
+
+/// Decode the given slice as ZigZag-encoded `i8` using the given options.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn i8_with(buf: &[u8], options: Options) -> Result<(i8, &[u8]), Error> {
+    match u8_with(buf, options) {
+        Ok((n, i)) => Ok((unzigzag!(n, i8), i)),
+        Err(e) => Err(e)
+    }
+}
+
+/// Decode the given slice as ZigZag-encoded `i16`.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn i16(buf: &[u8]) -> Result<(i16, &[u8]), Error> {
+    i16_with(buf, Options::new())
+}
+
+/// Decode the given slice as ZigZag-encoded `i16` using the given options.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn i16_with(buf: &[u8], options: Options) -> Result<(i16, &[u8]), Error> {
+    match u16_with(buf, options) {
+        Ok((n, i)) => Ok((unzigzag!(n, i16), i)),
+        Err(e) => Err(e)
+    }
+}
+
+/// Decode the given slice as ZigZag-encoded `i32`.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn i32(buf: &[u8]) -> Result<(i32, &[u8]), Error> {
+    i32_with(buf, Options::new())
+}
+
+/// Decode the given slice as ZigZag-encoded `i32` using the given options.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn i32_with(buf: &[u8], options: Options) -> Result<(i32, &[u8]), Error> {
+    match u32_with(buf, options) {
+        Ok((n, i)) => Ok((unzigzag!(n, i32), i)),
+        Err(e) => Err(e)
+    }
+}
+
+/// Decode the given slice as ZigZag-encoded `i64`.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn i64(buf: &[u8]) -> Result<(i64, &[u8]), Error> {
+    i64_with(buf, Options::new())
+}
+
+/// Decode the given slice as ZigZag-encoded `i64` using the given options.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn i64_with(buf: &[u8], options: Options) -> Result<(i64, &[u8]), Error> {
+    match u64_with(buf, options) {
+        Ok((n, i)) => Ok((unzigzag!(n, i64), i)),
+        Err(e) => Err(e)
+    }
+}
+
+/// Decode the given slice as ZigZag-encoded `i128`.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn i128(buf: &[u8]) -> Result<(i128, &[u8]), Error> {
+    i128_with(buf, Options::new())
+}
+
+/// Decode the given slice as ZigZag-encoded `i128` using the given options.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+pub const fn i128_with(buf: &[u8], options: Options) -> Result<(i128, &[u8]), Error> {
+    match u128_with(buf, options) {
+        Ok((n, i)) => Ok((unzigzag!(n, i128), i)),
+        Err(e) => Err(e)
+    }
+}
+
+/// Decode the given slice as ZigZag-encoded `isize`.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+#[cfg(target_pointer_width = "64")]
+pub const fn isize(buf: &[u8]) -> Result<(isize, &[u8]), Error> {
+    isize_with(buf, Options::new())
+}
+
+/// Decode the given slice as ZigZag-encoded `isize` using the given options.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+#[cfg(target_pointer_width = "64")]
+pub const fn isize_with(buf: &[u8], options: Options) -> Result<(isize, &[u8]), Error> {
+    match i64_with(buf, options) {
+        Ok((n, i)) => Ok((n as isize, i)),
+        Err(e) => Err(e)
+    }
+}
+
+/// Decode the given slice as ZigZag-encoded `isize`.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+#[cfg(target_pointer_width = "32")]
+pub const fn isize(buf: &[u8]) -> Result<(isize, &[u8]), Error> {
+    isize_with(buf, Options::new())
+}
+
+/// Decode the given slice as ZigZag-encoded `isize` using the given options.
+///
+/// Returns the value and the remaining slice.
+#[inline]
+#[cfg(target_pointer_width = "32")]
+pub const fn isize_with(buf: &[u8], options: Options) -> Result<(isize, &[u8]), Error> {
+    match i32_with(buf, options) {
+        Ok((n, i)) => Ok((n as isize, i)),
+        Err(e) => Err(e)
+    }
+}
+
+macro_rules! gen_nonzero {
+    ($($name:ident, $with:ident, $biased:ident, $biased_with:ident, $decode:ident, $d:expr, $t:ident);*) => {
+        $(
+            #[doc = " Decode the given slice as "]
+            #[doc = $d]
+            #[doc = ".\n\n"]
+            #[doc = " Returns the value and the remaining slice. A zero value is rejected"]
+            #[doc = " with [`ErrorKind::Zero`]."]
+            #[inline]
+            pub const fn $name(buf: &[u8]) -> Result<($t, &[u8]), Error> {
+                $with(buf, Options::new())
+            }
+
+            #[doc = " Decode the given slice as "]
+            #[doc = $d]
+            #[doc = " using the given options.\n\n"]
+            #[doc = " Returns the value and the remaining slice. A zero value is rejected"]
+            #[doc = " with [`ErrorKind::Zero`]."]
+            #[inline]
+            pub const fn $with(buf: &[u8], options: Options) -> Result<($t, &[u8]), Error> {
+                match $decode(buf, options) {
+                    Ok((n, rest)) => match $t::new(n) {
+                        Some(n) => Ok((n, rest)),
+                        None => {
+                            let len = buf.len() - rest.len();
+                            Err(Error::new(ErrorKind::Zero, len - 1, len))
+                        }
+                    },
+                    Err(e) => Err(e)
+                }
+            }
+
+            #[doc = " Decode the given slice as "]
+            #[doc = $d]
+            #[doc = " which has been encoded biased, i.e. as `n - 1`.\n\n"]
+            #[doc = " Returns the value and the remaining slice."]
+            #[inline]
+            pub const fn $biased(buf: &[u8]) -> Result<($t, &[u8]), Error> {
+                $biased_with(buf, Options::new())
+            }
+
+            #[doc = " Decode the given slice as "]
+            #[doc = $d]
+            #[doc = " which has been encoded biased, i.e. as `n - 1`, using the given options.\n\n"]
+            #[doc = " Returns the value and the remaining slice."]
+            #[inline]
+            pub const fn $biased_with(buf: &[u8], options: Options) -> Result<($t, &[u8]), Error> {
+                match $decode(buf, options) {
+                    // Only the maximum value wraps around to zero.
+                    Ok((n, rest)) => match $t::new(n.wrapping_add(1)) {
+                        Some(n) => Ok((n, rest)),
+                        None => {
+                            let len = buf.len() - rest.len();
+                            Err(Error::new(ErrorKind::ExcessBits, len - 1, len))
+                        }
+                    },
+                    Err(e) => Err(e)
+                }
+            }
+        )*
+    }
 }
+
+gen_nonzero! {
+    nonzero_u8,    nonzero_u8_with,    nonzero_u8_biased,    nonzero_u8_biased_with,    u8_with,    "`NonZeroU8`",    NonZeroU8;
+    nonzero_u16,   nonzero_u16_with,   nonzero_u16_biased,   nonzero_u16_biased_with,   u16_with,   "`NonZeroU16`",   NonZeroU16;
+    nonzero_u32,   nonzero_u32_with,   nonzero_u32_biased,   nonzero_u32_biased_with,   u32_with,   "`NonZeroU32`",   NonZeroU32;
+    nonzero_u64,   nonzero_u64_with,   nonzero_u64_biased,   nonzero_u64_biased_with,   u64_with,   "`NonZeroU64`",   NonZeroU64;
+    nonzero_u128,  nonzero_u128_with,  nonzero_u128_biased,  nonzero_u128_biased_with,  u128_with,  "`NonZeroU128`",  NonZeroU128;
+    nonzero_usize, nonzero_usize_with, nonzero_usize_biased, nonzero_usize_biased_with, usize_with, "`NonZeroUsize`", NonZeroUsize
+}
+
+macro_rules! gen_padded {
+    ($($name:ident, $with:ident, $d:expr, $t:ident);*) => {
+        $(
+            #[doc = " Decode the given slice as "]
+            #[doc = $d]
+            #[doc = ", accepting encodings padded to at most `width` bytes.\n\n"]
+            #[doc = " Returns the value and the remaining slice."]
+            #[inline]
+            pub const fn $name(buf: &[u8], width: usize) -> Result<($t, &[u8]), Error> {
+                $with(buf, Options::new().with_padded_len(width))
+            }
+        )*
+    }
+}
+
+gen_padded! {
+    u8_padded,    u8_with,    "`u8`",                 u8;
+    u16_padded,   u16_with,   "`u16`",                u16;
+    u32_padded,   u32_with,   "`u32`",                u32;
+    u64_padded,   u64_with,   "`u64`",                u64;
+    u128_padded,  u128_with,  "`u128`",               u128;
+    usize_padded, usize_with, "`usize`",              usize;
+    i8_padded,    i8_with,    "ZigZag-encoded `i8`",    i8;
+    i16_padded,   i16_with,   "ZigZag-encoded `i16`",   i16;
+    i32_padded,   i32_with,   "ZigZag-encoded `i32`",   i32;
+    i64_padded,   i64_with,   "ZigZag-encoded `i64`",   i64;
+    i128_padded,  i128_with,  "ZigZag-encoded `i128`",  i128;
+    isize_padded, isize_with, "ZigZag-encoded `isize`", isize
+}
+
+/// Decode consecutive varints from the given slice into `out`.
+///
+/// Decoding stops when `out` is full or the input is exhausted. Returns the
+/// number of decoded values, which are stored at the start of `out`, and the
+/// remaining slice.
+#[inline]
+pub fn slice<'a, T: Varint>(input: &'a [u8], out: &mut [T]) -> Result<(usize, &'a [u8]), SliceError> {
+    let mut rest = input;
+    let mut i = 0;
+    while i < out.len() && !rest.is_empty() {
+        // While 8 bytes remain, decode a word at a time without checking the
+        // length of the input per byte. Varints which are too long or
+        // invalid are left to the checked decoder.
+        if rest.len() >= 8 {
+            if let (n, Some(len)) = decode_word(rest) {
+                if len == 1 || rest[len - 1] != 0 {
+                    if let Some(n) = T::from_word(n) {
+                        out[i] = n;
+                        rest = &rest[len ..];
+                        i += 1;
+                        continue
+                    }
+                }
+            }
+        }
+        match T::decode_from(rest) {
+            Ok((n, r)) => {
+                out[i] = n;
+                rest = r
+            }
+            Err(error) => {
+                let offset = input.len() - rest.len();
+                return Err(SliceError { decoded: i, offset, error: error.shift(offset) })
+            }
+        }
+        i += 1
+    }
+    Ok((i, rest))
+}
+
+macro_rules! gen_slice {
+    ($($name:ident, $d:expr, $t:ident);*) => {
+        $(
+            #[doc = " Decode consecutive "]
+            #[doc = $d]
+            #[doc = " values from the given slice into `out`.\n\n"]
+            #[doc = " See [`slice()`] for details."]
+            #[inline]
+            pub fn $name<'a>(input: &'a [u8], out: &mut [$t]) -> Result<(usize, &'a [u8]), SliceError> {
+                slice(input, out)
+            }
+        )*
+    }
+}
+
+gen_slice! {
+    u8_slice,    "`u8`",    u8;
+    u16_slice,   "`u16`",   u16;
+    u32_slice,   "`u32`",   u32;
+    u64_slice,   "`u64`",   u64;
+    u128_slice,  "`u128`",  u128;
+    usize_slice, "`usize`", usize;
+    i8_slice,    "`i8`",    i8;
+    i16_slice,   "`i16`",   i16;
+    i32_slice,   "`i32`",   i32;
+    i64_slice,   "`i64`",   i64;
+    i128_slice,  "`i128`",  i128;
+    isize_slice, "`isize`", isize
+}
+
+/// Iterator over consecutive varints in a byte slice.
+///
+/// Yields the decoded values until the end of input is reached. If a varint
+/// fails to decode, the error is yielded and the iteration stops, leaving the
+/// remaining slice at the start of the invalid varint. The offset of the error
+/// is relative to the start of the whole input.
+#[derive(Debug)]
+pub struct Iter<'a, T> {
+    input: &'a [u8],
+    rest: &'a [u8],
+    failed: bool,
+    _ty: PhantomData<fn() -> T>
+}
+
+impl<'a, T> Iter<'a, T> {
+    /// Create an iterator over the varints in the given slice.
+    pub fn new(input: &'a [u8]) -> Self {
+        Iter { input, rest: input, failed: false, _ty: PhantomData }
+    }
+
+    /// Return the part of the input which has not been decoded yet.
+    pub fn remainder(&self) -> &'a [u8] {
+        self.rest
+    }
+
+    /// Return the offset of the next varint within the input.
+    pub fn offset(&self) -> usize {
+        self.input.len() - self.rest.len()
+    }
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter { input: self.input, rest: self.rest, failed: self.failed, _ty: PhantomData }
+    }
+}
+
+impl<T: Varint> Iterator for Iter<'_, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.rest.is_empty() {
+            return None
+        }
+        match T::decode_from(self.rest) {
+            Ok((n, rest)) => {
+                self.rest = rest;
+                Some(Ok(n))
+            }
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e.shift(self.offset())))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.failed {
+            (0, Some(0))
+        } else {
+            // Every varint occupies at least one byte.
+            (0, Some(self.rest.len()))
+        }
+    }
+}
+
+impl<T: Varint> FusedIterator for Iter<'_, T> {}
@@ -18,32 +18,186 @@
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 //! Basic unsigned-varint encoding.
+//!
+//! Signed integers are mapped to unsigned ones with ZigZag encoding before
+//! being encoded, so that values with a small absolute value also have a
+//! short encoding (0 => 0, -1 => 1, 1 => 2, -2 => 3, ...).
 
+use crate::{decode, Varint};
+use core::{
+    fmt,
+    iter::FusedIterator,
+    ops::Deref,
+    num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize}
+};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Possible encoding errors.
+///
+/// **Note**: The `std` feature is required for the `std::error::Error` impl.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The output buffer is too small for the encoded value.
+    BufferTooSmall {
+        /// The number of bytes the encoded value occupies.
+        required: usize
+    },
+    /// The input to re-encode is not a complete varint.
+    Decode(decode::Error),
+    /// The padded width is too small for the encoded value.
+    WidthTooSmall {
+        /// The number of bytes the minimal encoding occupies.
+        required: usize
+    },
+    /// The padded width exceeds the maximum length of the type.
+    WidthTooLarge {
+        /// The maximum length of the type.
+        max: usize
+    },
+    /// The mark passed to a reverse writer is beyond its current length.
+    InvalidMark {
+        /// The given mark.
+        mark: usize,
+        /// The number of bytes written so far.
+        len: usize
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::BufferTooSmall { required } => {
+                write!(f, "buffer too small, {} bytes required", required)
+            }
+            Error::Decode(e) => write!(f, "invalid input: {}", e),
+            Error::WidthTooSmall { required } => {
+                write!(f, "width too small, {} bytes required", required)
+            }
+            Error::WidthTooLarge { max } => {
+                write!(f, "width too large, at most {} bytes allowed", max)
+            }
+            Error::InvalidMark { mark, len } => {
+                write!(f, "invalid mark {}, only {} bytes written", mark, len)
+            }
+        }
+    }
+}
+
+/// Only available when the feature `std` is present.
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Decode(e) => Some(e),
+            _ => None
+        }
+    }
+}
+
+// One- and two-byte encodings are special-cased. Longer ones are written
+// without testing every byte for being the last one: all bytes of the buffer
+// are written with the continuation bit set, which is then cleared in the
+// last byte as determined by the encoded length.
 macro_rules! encode {
-    ($number:expr, $buf:expr) => {{
+    ($number:expr, $buf:expr, $len:ident) => {{
+        let n = $number;
+        if n < 0x80 {
+            $buf[0] = n as u8;
+            &$buf[.. 1]
+        } else if (n as u128) < 0x4000 {
+            $buf[0] = n as u8 | 0x80;
+            $buf[1] = (n >> 7) as u8;
+            &$buf[.. 2]
+        } else {
+            let len = $len(n);
+            for (i, b) in $buf.iter_mut().enumerate() {
+                *b = (n >> (i * 7)) as u8 | 0x80
+            }
+            $buf[len - 1] &= 0x7f;
+            &$buf[.. len]
+        }
+    }}
+}
+
+// Writes only the bytes of the encoding, leaving the rest of `$buf` untouched.
+macro_rules! encode_slice {
+    ($number:expr, $buf:expr, $len:ident) => {{
         let mut n = $number;
-        let mut i = 0;
-        for b in $buf.iter_mut() {
+        let len = $len(n);
+        if $buf.len() < len {
+            return Err(Error::BufferTooSmall { required: len })
+        }
+        for b in &mut $buf[.. len] {
             *b = n as u8 | 0x80;
+            n >>= 7
+        }
+        $buf[len - 1] &= 0x7f;
+        Ok(len)
+    }}
+}
+
+macro_rules! encode_padded {
+    ($number:expr, $width:expr, $buf:expr, $len:ident, $max:ident) => {{
+        let n = $number;
+        let width = $width;
+        if width > $max {
+            return Err(Error::WidthTooLarge { max: $max })
+        }
+        if $len(n) > width {
+            return Err(Error::WidthTooSmall { required: $len(n) })
+        }
+        if $buf.len() < width {
+            return Err(Error::BufferTooSmall { required: width })
+        }
+        // Groups beyond the encoded length are zero.
+        for (i, b) in $buf[.. width].iter_mut().enumerate() {
+            *b = (n >> (i * 7)) as u8 | 0x80
+        }
+        $buf[width - 1] &= 0x7f;
+        Ok(width)
+    }}
+}
+
+macro_rules! encode_array {
+    ($number:expr, $len:ident, $n:expr) => {{
+        let mut n = $number;
+        assert!($n == $len(n), "array length differs from the encoded length");
+        let mut buf = [0; $n];
+        let mut i = 0;
+        while i < $n {
+            buf[i] = n as u8 | 0x80;
             n >>= 7;
-            if n == 0 {
-                *b &= 0x7f;
-                break
-            }
             i += 1
         }
-        debug_assert_eq!(n, 0);
-        &$buf[0..=i]
+        buf[$n - 1] &= 0x7f;
+        buf
+    }}
+}
+
+macro_rules! zigzag {
+    ($number:expr, $unsigned:ident) => {{
+        let n = $number;
+        ((n << 1) ^ (n >> ($unsigned::BITS - 1))) as $unsigned
     }}
 }
 
+macro_rules! encoded_len {
+    ($number:expr, $typ:ident) => {{
+        // `| 1` ensures that zero is counted as one significant bit.
+        let bits = $typ::BITS - ($number | 1).leading_zeros();
+        bits.div_ceil(7) as usize
+    }}
+}
 
 /// Encode the given `u8` into the given byte array.
 ///
 /// Returns the slice of encoded bytes.
 #[inline]
 pub fn u8(number: u8, buf: &mut [u8; U8_LEN]) -> &[u8] {
-    encode!(number, buf)
+    encode!(number, buf, u8_len)
 }
 
 /// Encode the given `u16` into the given byte array.
@@ -51,7 +205,7 @@ pub fn u8(number: u8, buf: &mut [u8; U8_LEN]) -> &[u8] {
 /// Returns the slice of encoded bytes.
 #[inline]
 pub fn u16(number: u16, buf: &mut [u8; U16_LEN]) -> &[u8] {
-    encode!(number, buf)
+    encode!(number, buf, u16_len)
 }
 
 /// Encode the given `u32` into the given byte array.
@@ -59,7 +213,7 @@ pub fn u16(number: u16, buf: &mut [u8; U16_LEN]) -> &[u8] {
 /// Returns the slice of encoded bytes.
 #[inline]
 pub fn u32(number: u32, buf: &mut [u8; U32_LEN]) -> &[u8] {
-    encode!(number, buf)
+    encode!(number, buf, u32_len)
 }
 
 /// Encode the given `u64` into the given byte array.
@@ -67,7 +221,7 @@ pub fn u32(number: u32, buf: &mut [u8; U32_LEN]) -> &[u8] {
 /// Returns the slice of encoded bytes.
 #[inline]
 pub fn u64(number: u64, buf: &mut [u8; U64_LEN]) -> &[u8] {
-    encode!(number, buf)
+    encode!(number, buf, u64_len)
 }
 
 /// Encode the given `u128` into the given byte array.
@@ -75,7 +229,7 @@ pub fn u64(number: u64, buf: &mut [u8; U64_LEN]) -> &[u8] {
 /// Returns the slice of encoded bytes.
 #[inline]
 pub fn u128(number: u128, buf: &mut [u8; U128_LEN]) -> &[u8] {
-    encode!(number, buf)
+    encode!(number, buf, u128_len)
 }
 
 /// Encode the given `usize` into the given byte array.
@@ -96,6 +250,668 @@ pub fn usize(number: usize, buf: &mut [u8; USIZE_LEN]) -> &[u8] {
     u32(number as u32, buf)
 }
 
+/// Encode the given `i8` into the given byte array.
+///
+/// Returns the slice of encoded bytes.
+#[inline]
+pub fn i8(number: i8, buf: &mut [u8; I8_LEN]) -> &[u8] {
+    u8(zigzag!(number, u8), buf)
+}
+
+/// Encode the given `i16` into the given byte array.
+///
+/// Returns the slice of encoded bytes.
+#[inline]
+pub fn i16(number: i16, buf: &mut [u8; I16_LEN]) -> &[u8] {
+    u16(zigzag!(number, u16), buf)
+}
+
+/// Encode the given `i32` into the given byte array.
+///
+/// Returns the slice of encoded bytes.
+#[inline]
+pub fn i32(number: i32, buf: &mut [u8; I32_LEN]) -> &[u8] {
+    u32(zigzag!(number, u32), buf)
+}
+
+/// Encode the given `i64` into the given byte array.
+///
+/// Returns the slice of encoded bytes.
+#[inline]
+pub fn i64(number: i64, buf: &mut [u8; I64_LEN]) -> &[u8] {
+    u64(zigzag!(number, u64), buf)
+}
+
+/// Encode the given `i128` into the given byte array.
+///
+/// Returns the slice of encoded bytes.
+#[inline]
+pub fn i128(number: i128, buf: &mut [u8; I128_LEN]) -> &[u8] {
+    u128(zigzag!(number, u128), buf)
+}
+
+/// Encode the given `isize` into the given byte array.
+///
+/// Returns the slice of encoded bytes.
+#[inline]
+#[cfg(target_pointer_width = "64")]
+pub fn isize(number: isize, buf: &mut [u8; ISIZE_LEN]) -> &[u8] {
+    i64(number as i64, buf)
+}
+
+/// Encode the given `isize` into the given byte array.
+///
+/// Returns the slice of encoded bytes.
+#[inline]
+#[cfg(target_pointer_width = "32")]
+pub fn isize(number: isize, buf: &mut [u8; ISIZE_LEN]) -> &[u8] {
+    i32(number as i32, buf)
+}
+
+/// Encode the given `u8` into the given byte slice.
+///
+/// Returns the number of bytes written to the start of `buf` or
+/// [`Error::BufferTooSmall`] if the encoding does not fit.
+#[inline]
+pub fn u8_into(number: u8, buf: &mut [u8]) -> Result<usize, Error> {
+    encode_slice!(number, buf, u8_len)
+}
+
+/// Encode the given `u16` into the given byte slice.
+///
+/// Returns the number of bytes written to the start of `buf` or
+/// [`Error::BufferTooSmall`] if the encoding does not fit.
+#[inline]
+pub fn u16_into(number: u16, buf: &mut [u8]) -> Result<usize, Error> {
+    encode_slice!(number, buf, u16_len)
+}
+
+/// Encode the given `u32` into the given byte slice.
+///
+/// Returns the number of bytes written to the start of `buf` or
+/// [`Error::BufferTooSmall`] if the encoding does not fit.
+#[inline]
+pub fn u32_into(number: u32, buf: &mut [u8]) -> Result<usize, Error> {
+    encode_slice!(number, buf, u32_len)
+}
+
+/// Encode the given `u64` into the given byte slice.
+///
+/// Returns the number of bytes written to the start of `buf` or
+/// [`Error::BufferTooSmall`] if the encoding does not fit.
+#[inline]
+pub fn u64_into(number: u64, buf: &mut [u8]) -> Result<usize, Error> {
+    encode_slice!(number, buf, u64_len)
+}
+
+/// Encode the given `u128` into the given byte slice.
+///
+/// Returns the number of bytes written to the start of `buf` or
+/// [`Error::BufferTooSmall`] if the encoding does not fit.
+#[inline]
+pub fn u128_into(number: u128, buf: &mut [u8]) -> Result<usize, Error> {
+    encode_slice!(number, buf, u128_len)
+}
+
+/// Encode the given `usize` into the given byte slice.
+///
+/// Returns the number of bytes written to the start of `buf` or
+/// [`Error::BufferTooSmall`] if the encoding does not fit.
+#[inline]
+#[cfg(target_pointer_width = "64")]
+pub fn usize_into(number: usize, buf: &mut [u8]) -> Result<usize, Error> {
+    u64_into(number as u64, buf)
+}
+
+/// Encode the given `usize` into the given byte slice.
+///
+/// Returns the number of bytes written to the start of `buf` or
+/// [`Error::BufferTooSmall`] if the encoding does not fit.
+#[inline]
+#[cfg(target_pointer_width = "32")]
+pub fn usize_into(number: usize, buf: &mut [u8]) -> Result<usize, Error> {
+    u32_into(number as u32, buf)
+}
+
+/// Encode the given `i8` into the given byte slice.
+///
+/// Returns the number of bytes written to the start of `buf` or
+/// [`Error::BufferTooSmall`] if the encoding does not fit.
+#[inline]
+pub fn i8_into(number: i8, buf: &mut [u8]) -> Result<usize, Error> {
+    u8_into(zigzag!(number, u8), buf)
+}
+
+/// Encode the given `i16` into the given byte slice.
+///
+/// Returns the number of bytes written to the start of `buf` or
+/// [`Error::BufferTooSmall`] if the encoding does not fit.
+#[inline]
+pub fn i16_into(number: i16, buf: &mut [u8]) -> Result<usize, Error> {
+    u16_into(zigzag!(number, u16), buf)
+}
+
+/// Encode the given `i32` into the given byte slice.
+///
+/// Returns the number of bytes written to the start of `buf` or
+/// [`Error::BufferTooSmall`] if the encoding does not fit.
+#[inline]
+pub fn i32_into(number: i32, buf: &mut [u8]) -> Result<usize, Error> {
+    u32_into(zigzag!(number, u32), buf)
+}
+
+/// Encode the given `i64` into the given byte slice.
+///
+/// Returns the number of bytes written to the start of `buf` or
+/// [`Error::BufferTooSmall`] if the encoding does not fit.
+#[inline]
+pub fn i64_into(number: i64, buf: &mut [u8]) -> Result<usize, Error> {
+    u64_into(zigzag!(number, u64), buf)
+}
+
+/// Encode the given `i128` into the given byte slice.
+///
+/// Returns the number of bytes written to the start of `buf` or
+/// [`Error::BufferTooSmall`] if the encoding does not fit.
+#[inline]
+pub fn i128_into(number: i128, buf: &mut [u8]) -> Result<usize, Error> {
+    u128_into(zigzag!(number, u128), buf)
+}
+
+/// Encode the given `isize` into the given byte slice.
+///
+/// Returns the number of bytes written to the start of `buf` or
+/// [`Error::BufferTooSmall`] if the encoding does not fit.
+#[inline]
+#[cfg(target_pointer_width = "64")]
+pub fn isize_into(number: isize, buf: &mut [u8]) -> Result<usize, Error> {
+    i64_into(number as i64, buf)
+}
+
+/// Encode the given `isize` into the given byte slice.
+///
+/// Returns the number of bytes written to the start of `buf` or
+/// [`Error::BufferTooSmall`] if the encoding does not fit.
+#[inline]
+#[cfg(target_pointer_width = "32")]
+pub fn isize_into(number: isize, buf: &mut [u8]) -> Result<usize, Error> {
+    i32_into(number as i32, buf)
+}
+
+macro_rules! gen_sinks {
+    ($($extend:ident, $vec:ident, $encode:ident, $buffer:ident, $d:expr, $t:ident);*) => {
+        $(
+            #[doc = " Encode the given "]
+            #[doc = $d]
+            #[doc = " and append it to the given sink."]
+            #[inline]
+            pub fn $extend<E: Extend<u8> + ?Sized>(number: $t, sink: &mut E) {
+                let mut buf = $buffer();
+                sink.extend($encode(number, &mut buf).iter().copied())
+            }
+
+            #[doc = " Encode the given "]
+            #[doc = $d]
+            #[doc = " and append it to the given `Vec`.\n\n"]
+            #[doc = " Only available when the feature `alloc` is present."]
+            #[inline]
+            #[cfg(feature = "alloc")]
+            pub fn $vec(number: $t, vec: &mut Vec<u8>) {
+                let mut buf = $buffer();
+                vec.extend_from_slice($encode(number, &mut buf))
+            }
+        )*
+    }
+}
+
+gen_sinks! {
+    u8_extend,    u8_vec,    u8,    u8_buffer,    "`u8`",    u8;
+    u16_extend,   u16_vec,   u16,   u16_buffer,   "`u16`",   u16;
+    u32_extend,   u32_vec,   u32,   u32_buffer,   "`u32`",   u32;
+    u64_extend,   u64_vec,   u64,   u64_buffer,   "`u64`",   u64;
+    u128_extend,  u128_vec,  u128,  u128_buffer,  "`u128`",  u128;
+    usize_extend, usize_vec, usize, usize_buffer, "`usize`", usize;
+    i8_extend,    i8_vec,    i8,    i8_buffer,    "`i8`",    i8;
+    i16_extend,   i16_vec,   i16,   i16_buffer,   "`i16`",   i16;
+    i32_extend,   i32_vec,   i32,   i32_buffer,   "`i32`",   i32;
+    i64_extend,   i64_vec,   i64,   i64_buffer,   "`i64`",   i64;
+    i128_extend,  i128_vec,  i128,  i128_buffer,  "`i128`",  i128;
+    isize_extend, isize_vec, isize, isize_buffer, "`isize`", isize
+}
+
+/// Return an iterator over the bytes of the encodings of the given values.
+///
+/// The values are encoded lazily, one at a time, like with [`u64()`].
+pub fn iter<I: IntoIterator<Item = u64>>(values: I) -> Iter<I::IntoIter> {
+    Iter { chunks: chunks(values), chunk: Chunk::default(), pos: 0 }
+}
+
+/// Return an iterator over the encodings of the given values.
+///
+/// Each item is a [`Chunk`] which dereferences to the encoded bytes of one
+/// value, encoded lazily like with [`u64()`].
+///
+/// The items are owned [`Chunk`]s rather than `&[u8]` slices because an
+/// [`Iterator`] cannot lend out borrows of its own buffer. A `Chunk` can be
+/// used wherever `&[u8]` or `AsRef<[u8]>` is expected.
+pub fn chunks<I: IntoIterator<Item = u64>>(values: I) -> Chunks<I::IntoIter> {
+    Chunks { values: values.into_iter() }
+}
+
+/// Iterator over the bytes of the encodings of a sequence of `u64` values.
+///
+/// Created by [`iter`].
+#[derive(Clone, Debug)]
+pub struct Iter<I> {
+    chunks: Chunks<I>,
+    chunk: Chunk,
+    pos: usize
+}
+
+impl<I: Iterator<Item = u64>> Iterator for Iter<I> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.pos == self.chunk.len() {
+            self.chunk = self.chunks.next()?;
+            self.pos = 0
+        }
+        self.pos += 1;
+        Some(self.chunk[self.pos - 1])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = self.chunk.len() - self.pos;
+        let (lower, upper) = self.chunks.size_hint();
+        (lower.saturating_add(rest), upper.and_then(|n| n.checked_mul(U64_LEN)?.checked_add(rest)))
+    }
+}
+
+impl<I: FusedIterator<Item = u64>> FusedIterator for Iter<I> {}
+
+/// Iterator over the encodings of a sequence of `u64` values.
+///
+/// Created by [`chunks`].
+#[derive(Clone, Debug)]
+pub struct Chunks<I> {
+    values: I
+}
+
+impl<I: Iterator<Item = u64>> Iterator for Chunks<I> {
+    type Item = Chunk;
+
+    fn next(&mut self) -> Option<Chunk> {
+        let mut chunk = Chunk::default();
+        chunk.len = u64(self.values.next()?, &mut chunk.buf).len() as u8;
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<I: ExactSizeIterator<Item = u64>> ExactSizeIterator for Chunks<I> {}
+
+impl<I: FusedIterator<Item = u64>> FusedIterator for Chunks<I> {}
+
+/// The encoding of a single `u64` value, as yielded by [`Chunks`].
+///
+/// Dereferences to the encoded bytes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Chunk {
+    buf: [u8; U64_LEN],
+    len: u8
+}
+
+impl Deref for Chunk {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buf[.. usize::from(self.len)]
+    }
+}
+
+impl AsRef<[u8]> for Chunk {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl PartialEq for Chunk {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for Chunk {}
+
+macro_rules! gen_nonzero {
+    ($($name:ident, $biased:ident, $len:ident, $biased_len:ident, $encode:ident, $encode_len:ident, $d:expr, $t:ident, $n:ident);*) => {
+        $(
+            #[doc = " Encode the given "]
+            #[doc = $d]
+            #[doc = " into the given byte array.\n\n"]
+            #[doc = " Returns the slice of encoded bytes."]
+            #[inline]
+            pub fn $name(number: $t, buf: &mut [u8; $n]) -> &[u8] {
+                $encode(number.get(), buf)
+            }
+
+            #[doc = " Encode the given "]
+            #[doc = $d]
+            #[doc = " biased, i.e. as `n - 1`, into the given byte array.\n\n"]
+            #[doc = " Powers of 128 then occupy one byte less.\n\n"]
+            #[doc = " Returns the slice of encoded bytes."]
+            #[inline]
+            pub fn $biased(number: $t, buf: &mut [u8; $n]) -> &[u8] {
+                $encode(number.get() - 1, buf)
+            }
+
+            #[doc = " Return the number of bytes the encoding of the given "]
+            #[doc = $d]
+            #[doc = " occupies."]
+            #[inline]
+            pub const fn $len(number: $t) -> usize {
+                $encode_len(number.get())
+            }
+
+            #[doc = " Return the number of bytes the biased encoding of the given "]
+            #[doc = $d]
+            #[doc = " occupies."]
+            #[inline]
+            pub const fn $biased_len(number: $t) -> usize {
+                $encode_len(number.get() - 1)
+            }
+        )*
+    }
+}
+
+gen_nonzero! {
+    nonzero_u8,    nonzero_u8_biased,    nonzero_u8_len,    nonzero_u8_biased_len,    u8,    u8_len,    "`NonZeroU8`",    NonZeroU8,    U8_LEN;
+    nonzero_u16,   nonzero_u16_biased,   nonzero_u16_len,   nonzero_u16_biased_len,   u16,   u16_len,   "`NonZeroU16`",   NonZeroU16,   U16_LEN;
+    nonzero_u32,   nonzero_u32_biased,   nonzero_u32_len,   nonzero_u32_biased_len,   u32,   u32_len,   "`NonZeroU32`",   NonZeroU32,   U32_LEN;
+    nonzero_u64,   nonzero_u64_biased,   nonzero_u64_len,   nonzero_u64_biased_len,   u64,   u64_len,   "`NonZeroU64`",   NonZeroU64,   U64_LEN;
+    nonzero_u128,  nonzero_u128_biased,  nonzero_u128_len,  nonzero_u128_biased_len,  u128,  u128_len,  "`NonZeroU128`",  NonZeroU128,  U128_LEN;
+    nonzero_usize, nonzero_usize_biased, nonzero_usize_len, nonzero_usize_biased_len, usize, usize_len, "`NonZeroUsize`", NonZeroUsize, USIZE_LEN
+}
+
+macro_rules! gen_padded {
+    ($($name:ident, $len:ident, $max:ident, $d:expr, $t:ident);*) => {
+        $(
+            #[doc = " Encode the given "]
+            #[doc = $d]
+            #[doc = " padded to exactly `width` bytes into the given byte slice.\n\n"]
+            #[doc = " Padded encodings are not minimal and must be decoded with"]
+            #[doc = " [`decode::Options::with_padded_len`].\n\n"]
+            #[doc = " Returns the number of bytes written to the start of `buf`,"]
+            #[doc = " [`Error::WidthTooSmall`] or [`Error::WidthTooLarge`] if the"]
+            #[doc = " value can not be encoded with `width` bytes or"]
+            #[doc = " [`Error::BufferTooSmall`] if `buf` is shorter than `width`."]
+            #[inline]
+            pub fn $name(number: $t, width: usize, buf: &mut [u8]) -> Result<usize, Error> {
+                encode_padded!(number, width, buf, $len, $max)
+            }
+        )*
+    };
+    (@zigzag $($name:ident, $unsigned:ident, $u:ident, $d:expr, $t:ident);*) => {
+        $(
+            #[doc = " Encode the given "]
+            #[doc = $d]
+            #[doc = " padded to exactly `width` bytes into the given byte slice.\n\n"]
+            #[doc = " See [`"]
+            #[doc = stringify!($unsigned)]
+            #[doc = "`] for details."]
+            #[inline]
+            pub fn $name(number: $t, width: usize, buf: &mut [u8]) -> Result<usize, Error> {
+                $unsigned(zigzag!(number, $u), width, buf)
+            }
+        )*
+    }
+}
+
+gen_padded! {
+    u8_padded,    u8_len,    U8_LEN,    "`u8`",    u8;
+    u16_padded,   u16_len,   U16_LEN,   "`u16`",   u16;
+    u32_padded,   u32_len,   U32_LEN,   "`u32`",   u32;
+    u64_padded,   u64_len,   U64_LEN,   "`u64`",   u64;
+    u128_padded,  u128_len,  U128_LEN,  "`u128`",  u128;
+    usize_padded, usize_len, USIZE_LEN, "`usize`", usize
+}
+
+gen_padded! {
+    @zigzag
+    i8_padded,    u8_padded,    u8,    "ZigZag-encoded `i8`",    i8;
+    i16_padded,   u16_padded,   u16,   "ZigZag-encoded `i16`",   i16;
+    i32_padded,   u32_padded,   u32,   "ZigZag-encoded `i32`",   i32;
+    i64_padded,   u64_padded,   u64,   "ZigZag-encoded `i64`",   i64;
+    i128_padded,  u128_padded,  u128,  "ZigZag-encoded `i128`",  i128;
+    isize_padded, usize_padded, usize, "ZigZag-encoded `isize`", isize
+}
+
+/// Encode the given `u8` into a byte array of exactly the encoded length.
+///
+/// In contrast to [`u8()`] this function can be used in `const` contexts,
+/// e.g. `const TAG: [u8; 2] = encode::u8_array(128);`.
+///
+/// # Panics
+///
+/// If `N` is not equal to [`u8_len`] of the given number. When evaluated
+/// at compile time this results in a compilation error.
+#[inline]
+pub const fn u8_array<const N: usize>(number: u8) -> [u8; N] {
+    encode_array!(number, u8_len, N)
+}
+
+/// Encode the given `u16` into a byte array of exactly the encoded length.
+///
+/// In contrast to [`u16()`] this function can be used in `const` contexts,
+/// e.g. `const TAG: [u8; 2] = encode::u16_array(128);`.
+///
+/// # Panics
+///
+/// If `N` is not equal to [`u16_len`] of the given number. When evaluated
+/// at compile time this results in a compilation error.
+#[inline]
+pub const fn u16_array<const N: usize>(number: u16) -> [u8; N] {
+    encode_array!(number, u16_len, N)
+}
+
+/// Encode the given `u32` into a byte array of exactly the encoded length.
+///
+/// In contrast to [`u32()`] this function can be used in `const` contexts,
+/// e.g. `const TAG: [u8; 2] = encode::u32_array(128);`.
+///
+/// # Panics
+///
+/// If `N` is not equal to [`u32_len`] of the given number. When evaluated
+/// at compile time this results in a compilation error.
+#[inline]
+pub const fn u32_array<const N: usize>(number: u32) -> [u8; N] {
+    encode_array!(number, u32_len, N)
+}
+
+/// Encode the given `u64` into a byte array of exactly the encoded length.
+///
+/// In contrast to [`u64()`] this function can be used in `const` contexts,
+/// e.g. `const TAG: [u8; 2] = encode::u64_array(128);`.
+///
+/// # Panics
+///
+/// If `N` is not equal to [`u64_len`] of the given number. When evaluated
+/// at compile time this results in a compilation error.
+#[inline]
+pub const fn u64_array<const N: usize>(number: u64) -> [u8; N] {
+    encode_array!(number, u64_len, N)
+}
+
+/// Concatenate the given slices into an array of their total length.
+///
+/// Used by [`varint_prefixed!`](crate::varint_prefixed).
+///
+/// # Panics
+///
+/// If `N` is not equal to the total length of the slices.
+#[doc(hidden)]
+pub const fn concat<const N: usize>(a: &[u8], b: &[u8]) -> [u8; N] {
+    assert!(N == a.len() + b.len(), "array length differs from the total length");
+    let mut buf = [0; N];
+    let mut i = 0;
+    while i < a.len() {
+        buf[i] = a[i];
+        i += 1
+    }
+    while i < N {
+        buf[i] = b[i - a.len()];
+        i += 1
+    }
+    buf
+}
+
+/// Encode the given `u128` into a byte array of exactly the encoded length.
+///
+/// In contrast to [`u128()`] this function can be used in `const` contexts,
+/// e.g. `const TAG: [u8; 2] = encode::u128_array(128);`.
+///
+/// # Panics
+///
+/// If `N` is not equal to [`u128_len`] of the given number. When evaluated
+/// at compile time this results in a compilation error.
+#[inline]
+pub const fn u128_array<const N: usize>(number: u128) -> [u8; N] {
+    encode_array!(number, u128_len, N)
+}
+
+/// Encode the given `usize` into a byte array of exactly the encoded length.
+///
+/// In contrast to [`usize()`] this function can be used in `const` contexts,
+/// e.g. `const TAG: [u8; 2] = encode::usize_array(128);`.
+///
+/// # Panics
+///
+/// If `N` is not equal to [`usize_len`] of the given number. When evaluated
+/// at compile time this results in a compilation error.
+#[inline]
+#[cfg(target_pointer_width = "64")]
+pub const fn usize_array<const N: usize>(number: usize) -> [u8; N] {
+    u64_array(number as u64)
+}
+
+/// Encode the given `usize` into a byte array of exactly the encoded length.
+///
+/// In contrast to [`usize()`] this function can be used in `const` contexts,
+/// e.g. `const TAG: [u8; 2] = encode::usize_array(128);`.
+///
+/// # Panics
+///
+/// If `N` is not equal to [`usize_len`] of the given number. When evaluated
+/// at compile time this results in a compilation error.
+#[inline]
+#[cfg(target_pointer_width = "32")]
+pub const fn usize_array<const N: usize>(number: usize) -> [u8; N] {
+    u32_array(number as u32)
+}
+
+/// Encode the given `i8` into a byte array of exactly the encoded length.
+///
+/// In contrast to [`i8()`] this function can be used in `const` contexts,
+/// e.g. `const TAG: [u8; 2] = encode::i8_array(64);`.
+///
+/// # Panics
+///
+/// If `N` is not equal to [`i8_len`] of the given number. When evaluated
+/// at compile time this results in a compilation error.
+#[inline]
+pub const fn i8_array<const N: usize>(number: i8) -> [u8; N] {
+    u8_array(zigzag!(number, u8))
+}
+
+/// Encode the given `i16` into a byte array of exactly the encoded length.
+///
+/// In contrast to [`i16()`] this function can be used in `const` contexts,
+/// e.g. `const TAG: [u8; 2] = encode::i16_array(64);`.
+///
+/// # Panics
+///
+/// If `N` is not equal to [`i16_len`] of the given number. When evaluated
+/// at compile time this results in a compilation error.
+#[inline]
+pub const fn i16_array<const N: usize>(number: i16) -> [u8; N] {
+    u16_array(zigzag!(number, u16))
+}
+
+/// Encode the given `i32` into a byte array of exactly the encoded length.
+///
+/// In contrast to [`i32()`] this function can be used in `const` contexts,
+/// e.g. `const TAG: [u8; 2] = encode::i32_array(64);`.
+///
+/// # Panics
+///
+/// If `N` is not equal to [`i32_len`] of the given number. When evaluated
+/// at compile time this results in a compilation error.
+#[inline]
+pub const fn i32_array<const N: usize>(number: i32) -> [u8; N] {
+    u32_array(zigzag!(number, u32))
+}
+
+/// Encode the given `i64` into a byte array of exactly the encoded length.
+///
+/// In contrast to [`i64()`] this function can be used in `const` contexts,
+/// e.g. `const TAG: [u8; 2] = encode::i64_array(64);`.
+///
+/// # Panics
+///
+/// If `N` is not equal to [`i64_len`] of the given number. When evaluated
+/// at compile time this results in a compilation error.
+#[inline]
+pub const fn i64_array<const N: usize>(number: i64) -> [u8; N] {
+    u64_array(zigzag!(number, u64))
+}
+
+/// Encode the given `i128` into a byte array of exactly the encoded length.
+///
+/// In contrast to [`i128()`] this function can be used in `const` contexts,
+/// e.g. `const TAG: [u8; 2] = encode::i128_array(64);`.
+///
+/// # Panics
+///
+/// If `N` is not equal to [`i128_len`] of the given number. When evaluated
+/// at compile time this results in a compilation error.
+#[inline]
+pub const fn i128_array<const N: usize>(number: i128) -> [u8; N] {
+    u128_array(zigzag!(number, u128))
+}
+
+/// Encode the given `isize` into a byte array of exactly the encoded length.
+///
+/// In contrast to [`isize()`] this function can be used in `const` contexts,
+/// e.g. `const TAG: [u8; 2] = encode::isize_array(64);`.
+///
+/// # Panics
+///
+/// If `N` is not equal to [`isize_len`] of the given number. When evaluated
+/// at compile time this results in a compilation error.
+#[inline]
+#[cfg(target_pointer_width = "64")]
+pub const fn isize_array<const N: usize>(number: isize) -> [u8; N] {
+    i64_array(number as i64)
+}
+
+/// Encode the given `isize` into a byte array of exactly the encoded length.
+///
+/// In contrast to [`isize()`] this function can be used in `const` contexts,
+/// e.g. `const TAG: [u8; 2] = encode::isize_array(64);`.
+///
+/// # Panics
+///
+/// If `N` is not equal to [`isize_len`] of the given number. When evaluated
+/// at compile time this results in a compilation error.
+#[inline]
+#[cfg(target_pointer_width = "32")]
+pub const fn isize_array<const N: usize>(number: isize) -> [u8; N] {
+    i32_array(number as i32)
+}
+
 /// Create new array buffer for encoding of `u8` values.
 #[inline]
 pub fn u8_buffer() -> [u8; U8_LEN] {
@@ -132,18 +948,402 @@ pub fn usize_buffer() -> [u8; USIZE_LEN] {
     [0; USIZE_LEN]
 }
 
+/// Create new array buffer for encoding of `i8` values.
+#[inline]
+pub fn i8_buffer() -> [u8; I8_LEN] {
+    [0; I8_LEN]
+}
 
-// Required lengths of encoding buffers:
+/// Create new array buffer for encoding of `i16` values.
+#[inline]
+pub fn i16_buffer() -> [u8; I16_LEN] {
+    [0; I16_LEN]
+}
+
+/// Create new array buffer for encoding of `i32` values.
+#[inline]
+pub fn i32_buffer() -> [u8; I32_LEN] {
+    [0; I32_LEN]
+}
+
+/// Create new array buffer for encoding of `i64` values.
+#[inline]
+pub fn i64_buffer() -> [u8; I64_LEN] {
+    [0; I64_LEN]
+}
+
+/// Create new array buffer for encoding of `i128` values.
+#[inline]
+pub fn i128_buffer() -> [u8; I128_LEN] {
+    [0; I128_LEN]
+}
 
-const U8_LEN: usize = 2;
-const U16_LEN: usize = 3;
-const U32_LEN: usize = 5;
-const U64_LEN: usize = 10;
-const U128_LEN: usize = 19;
+/// Create new array buffer for encoding of `isize` values.
+#[inline]
+pub fn isize_buffer() -> [u8; ISIZE_LEN] {
+    [0; ISIZE_LEN]
+}
 
+
+/// Return the number of bytes the encoding of the given `u8` occupies.
+#[inline]
+pub const fn u8_len(number: u8) -> usize {
+    encoded_len!(number, u8)
+}
+
+/// Return the number of bytes the encoding of the given `u16` occupies.
+#[inline]
+pub const fn u16_len(number: u16) -> usize {
+    encoded_len!(number, u16)
+}
+
+/// Return the number of bytes the encoding of the given `u32` occupies.
+#[inline]
+pub const fn u32_len(number: u32) -> usize {
+    encoded_len!(number, u32)
+}
+
+/// Return the number of bytes the encoding of the given `u64` occupies.
+#[inline]
+pub const fn u64_len(number: u64) -> usize {
+    encoded_len!(number, u64)
+}
+
+/// Return the number of bytes the encoding of the given `u128` occupies.
+#[inline]
+pub const fn u128_len(number: u128) -> usize {
+    encoded_len!(number, u128)
+}
+
+/// Return the number of bytes the encoding of the given `usize` occupies.
+#[inline]
+#[cfg(target_pointer_width = "64")]
+pub const fn usize_len(number: usize) -> usize {
+    u64_len(number as u64)
+}
+
+/// Return the number of bytes the encoding of the given `usize` occupies.
+#[inline]
+#[cfg(target_pointer_width = "32")]
+pub const fn usize_len(number: usize) -> usize {
+    u32_len(number as u32)
+}
+
+/// Return the number of bytes the encoding of the given `i8` occupies.
+#[inline]
+pub const fn i8_len(number: i8) -> usize {
+    u8_len(zigzag!(number, u8))
+}
+
+/// Return the number of bytes the encoding of the given `i16` occupies.
+#[inline]
+pub const fn i16_len(number: i16) -> usize {
+    u16_len(zigzag!(number, u16))
+}
+
+/// Return the number of bytes the encoding of the given `i32` occupies.
+#[inline]
+pub const fn i32_len(number: i32) -> usize {
+    u32_len(zigzag!(number, u32))
+}
+
+/// Return the number of bytes the encoding of the given `i64` occupies.
+#[inline]
+pub const fn i64_len(number: i64) -> usize {
+    u64_len(zigzag!(number, u64))
+}
+
+/// Return the number of bytes the encoding of the given `i128` occupies.
+#[inline]
+pub const fn i128_len(number: i128) -> usize {
+    u128_len(zigzag!(number, u128))
+}
+
+/// Return the number of bytes the encoding of the given `isize` occupies.
+#[inline]
+#[cfg(target_pointer_width = "64")]
+pub const fn isize_len(number: isize) -> usize {
+    i64_len(number as i64)
+}
+
+/// Return the number of bytes the encoding of the given `isize` occupies.
+#[inline]
+#[cfg(target_pointer_width = "32")]
+pub const fn isize_len(number: isize) -> usize {
+    i32_len(number as i32)
+}
+
+/// Return the minimal length and the actual length of the varint at the start
+/// of `input`.
+fn minimal_len(input: &[u8]) -> Result<(usize, usize), decode::Error> {
+    let len = decode::encoded_len(input).ok_or(decode::Error::insufficient(input.len()))?;
+    // Drop trailing zero groups but keep at least one byte.
+    let min = input[.. len].iter().rposition(|b| b & 0x7f != 0).map_or(1, |i| i + 1);
+    Ok((min, len))
+}
+
+/// Re-encode the varint at the start of `input` into its minimal form.
+///
+/// Trailing zero groups, as written by encoders which pad varints, are
+/// removed. The value is not checked against any integer type, i.e. the
+/// result can be decoded with the same types as the input, except that
+/// [`decode::ErrorKind::NotMinimal`] no longer occurs.
+///
+/// Returns the number of bytes written to the start of `out` and the
+/// remaining input, [`Error::Decode`] if `input` does not start with a
+/// complete varint or [`Error::BufferTooSmall`] if the result does not fit.
+pub fn canonicalize<'a>(input: &'a [u8], out: &mut [u8]) -> Result<(usize, &'a [u8]), Error> {
+    let (min, len) = minimal_len(input).map_err(Error::Decode)?;
+    if out.len() < min {
+        return Err(Error::BufferTooSmall { required: min })
+    }
+    out[.. min].copy_from_slice(&input[.. min]);
+    out[min - 1] &= 0x7f;
+    Ok((min, input.split_at(len).1))
+}
+
+/// Re-encode all consecutive varints in `input` into their minimal form.
+///
+/// Like [`canonicalize`] but for a sequence of varints. The output is never
+/// longer than the input. Returns the number of bytes written to the start
+/// of `out`. Decoding errors report offsets relative to the whole input.
+pub fn canonicalize_all(input: &[u8], out: &mut [u8]) -> Result<usize, Error> {
+    let mut rest = input;
+    let mut written = 0;
+    while !rest.is_empty() {
+        let offset = input.len() - rest.len();
+        match canonicalize(rest, &mut out[written ..]) {
+            Ok((n, r)) => {
+                written += n;
+                rest = r
+            }
+            Err(Error::Decode(e)) => return Err(Error::Decode(e.shift(offset))),
+            Err(_) => {
+                // The buffer is too small, determine the total length required.
+                let mut required = written;
+                while !rest.is_empty() {
+                    let offset = input.len() - rest.len();
+                    let (min, len) = minimal_len(rest).map_err(|e| Error::Decode(e.shift(offset)))?;
+                    required += min;
+                    rest = &rest[len ..]
+                }
+                return Err(Error::BufferTooSmall { required })
+            }
+        }
+    }
+    Ok(written)
+}
+
+/// A writer which fills a byte slice from the end.
+///
+/// Writing back to front allows to prefix data with its length after the
+/// data has been written, e.g. for nested length-prefixed messages:
+///
+/// ```
+/// use unsigned_varint::encode::RevWriter;
+///
+/// let mut buf = [0; 8];
+/// let mut w = RevWriter::new(&mut buf);
+/// let mark = w.len();
+/// w.put_slice(b"body").unwrap();
+/// w.put_len_prefix(mark).unwrap();
+/// assert_eq!(b"\x04body", w.as_slice());
+/// ```
+#[derive(Debug)]
+pub struct RevWriter<'a> {
+    buf: &'a mut [u8],
+    start: usize
+}
+
+impl<'a> RevWriter<'a> {
+    /// Create a writer which fills the given slice from the end.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        let start = buf.len();
+        RevWriter { buf, start }
+    }
+
+    /// The number of bytes written so far.
+    pub fn len(&self) -> usize {
+        self.buf.len() - self.start
+    }
+
+    /// Has nothing been written yet?
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The bytes written so far, i.e. the end of the underlying slice.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf[self.start ..]
+    }
+
+    /// Consume this writer and return the bytes written.
+    pub fn into_slice(self) -> &'a mut [u8] {
+        let RevWriter { buf, start } = self;
+        &mut buf[start ..]
+    }
+
+    /// Write the given bytes in front of the bytes written so far.
+    ///
+    /// Returns [`Error::BufferTooSmall`] with the total length required if
+    /// the bytes do not fit. Nothing is written in this case.
+    pub fn put_slice(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        if bytes.len() > self.start {
+            return Err(Error::BufferTooSmall { required: self.len() + bytes.len() })
+        }
+        self.start -= bytes.len();
+        self.buf[self.start .. self.start + bytes.len()].copy_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Write the encoding of the given number in front of the bytes written
+    /// so far.
+    ///
+    /// Returns the number of bytes of the encoding.
+    pub fn put_varint<T: Varint>(&mut self, number: T) -> Result<usize, Error> {
+        let mut buf = T::Buffer::default();
+        let bytes = number.encode_into(&mut buf);
+        self.put_slice(bytes)?;
+        Ok(bytes.len())
+    }
+
+    /// Write the number of bytes written since `mark`, a previous value of
+    /// [`RevWriter::len`], as varint in front of the bytes written so far.
+    ///
+    /// Returns the number of bytes of the encoding, or [`Error::InvalidMark`]
+    /// if `mark` is greater than [`RevWriter::len`].
+    pub fn put_len_prefix(&mut self, mark: usize) -> Result<usize, Error> {
+        let len = self.len();
+        match len.checked_sub(mark) {
+            Some(n) => self.put_varint(n),
+            None => Err(Error::InvalidMark { mark, len })
+        }
+    }
+}
+
+/// A growable buffer which is filled from the end.
+///
+/// Like [`RevWriter`] but backed by a `Vec<u8>` which grows as needed.
+///
+/// **Note**: Only available if the feature `alloc` is present.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default)]
+pub struct RevVec {
+    buf: Vec<u8>,
+    start: usize
+}
+
+#[cfg(feature = "alloc")]
+impl RevVec {
+    /// Create an empty buffer.
+    pub fn new() -> Self {
+        RevVec::default()
+    }
+
+    /// Create an empty buffer which can hold `n` bytes without growing.
+    pub fn with_capacity(n: usize) -> Self {
+        RevVec { buf: alloc::vec![0; n], start: n }
+    }
+
+    /// The number of bytes written so far.
+    pub fn len(&self) -> usize {
+        self.buf.len() - self.start
+    }
+
+    /// Has nothing been written yet?
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The bytes written so far.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf[self.start ..]
+    }
+
+    /// Consume this buffer and return the bytes written.
+    pub fn into_vec(mut self) -> Vec<u8> {
+        self.buf.drain(.. self.start);
+        self.buf
+    }
+
+    /// Write the given bytes in front of the bytes written so far.
+    pub fn put_slice(&mut self, bytes: &[u8]) {
+        if bytes.len() > self.start {
+            // Move the data to the end of a buffer of at least twice the size.
+            let len = self.len();
+            let cap = core::cmp::max(2 * self.buf.len(), len + bytes.len());
+            let mut buf = alloc::vec![0; cap];
+            buf[cap - len ..].copy_from_slice(self.as_slice());
+            self.buf = buf;
+            self.start = cap - len
+        }
+        self.start -= bytes.len();
+        self.buf[self.start .. self.start + bytes.len()].copy_from_slice(bytes)
+    }
+
+    /// Write the encoding of the given number in front of the bytes written
+    /// so far.
+    ///
+    /// Returns the number of bytes of the encoding.
+    pub fn put_varint<T: Varint>(&mut self, number: T) -> usize {
+        let mut buf = T::Buffer::default();
+        let bytes = number.encode_into(&mut buf);
+        self.put_slice(bytes);
+        bytes.len()
+    }
+
+    /// Write the number of bytes written since `mark`, a previous value of
+    /// [`RevVec::len`], as varint in front of the bytes written so far.
+    ///
+    /// Returns the number of bytes of the encoding, or [`Error::InvalidMark`]
+    /// if `mark` is greater than [`RevVec::len`].
+    pub fn put_len_prefix(&mut self, mark: usize) -> Result<usize, Error> {
+        let len = self.len();
+        match len.checked_sub(mark) {
+            Some(n) => Ok(self.put_varint(n)),
+            None => Err(Error::InvalidMark { mark, len })
+        }
+    }
+}
+
+/// Maximum number of bytes an encoded `u8` occupies.
+pub const U8_LEN: usize = 2;
+
+/// Maximum number of bytes an encoded `u16` occupies.
+pub const U16_LEN: usize = 3;
+
+/// Maximum number of bytes an encoded `u32` occupies.
+pub const U32_LEN: usize = 5;
+
+/// Maximum number of bytes an encoded `u64` occupies.
+pub const U64_LEN: usize = 10;
+
+/// Maximum number of bytes an encoded `u128` occupies.
+pub const U128_LEN: usize = 19;
+
+/// Maximum number of bytes an encoded `usize` occupies.
 #[cfg(target_pointer_width = "64")]
-const USIZE_LEN: usize = U64_LEN;
+pub const USIZE_LEN: usize = U64_LEN;
 
+/// Maximum number of bytes an encoded `usize` occupies.
 #[cfg(target_pointer_width = "32")]
-const USIZE_LEN: usize = U32_LEN;
+pub const USIZE_LEN: usize = U32_LEN;
+
+/// Maximum number of bytes an encoded `i8` occupies.
+pub const I8_LEN: usize = U8_LEN;
+
+/// Maximum number of bytes an encoded `i16` occupies.
+pub const I16_LEN: usize = U16_LEN;
+
+/// Maximum number of bytes an encoded `i32` occupies.
+pub const I32_LEN: usize = U32_LEN;
+
+/// Maximum number of bytes an encoded `i64` occupies.
+pub const I64_LEN: usize = U64_LEN;
+
+/// Maximum number of bytes an encoded `i128` occupies.
+pub const I128_LEN: usize = U128_LEN;
+
+/// Maximum number of bytes an encoded `isize` occupies.
+pub const ISIZE_LEN: usize = USIZE_LEN;
 
@@ -19,85 +19,189 @@
 
 //! Decode using [`std::io::Read`] types.
 
-use crate::{decode, encode};
-use std::{fmt, io};
+use crate::{decode::{self, ErrorKind, Options}, encode::U64_LEN, Varint};
+use std::{
+    fmt,
+    io,
+    num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize}
+};
+
+/// Try to read and decode a [`Varint`] from the given `Read` type.
+pub fn read<T: Varint, R: io::Read>(reader: R) -> Result<T, ReadError> {
+    read_with(reader, Options::new())
+}
+
+/// Try to read and decode a [`Varint`] from the given `Read` type using the
+/// given options.
+///
+/// At most as many bytes as permitted by the options are read.
+pub fn read_with<T: Varint, R: io::Read>(reader: R, options: Options) -> Result<T, ReadError> {
+    let mut b = T::Buffer::default();
+    let b = b.as_mut();
+    let max = options.max_len(b.len());
+    let len = read_bytes(reader, &mut b[.. max], 0)?;
+    decode_read(&b[.. len], |b| T::decode_with(b, options))
+}
+
+/// Try to read and decode a `u64` of at most `max` from the given `Read`
+/// type.
+///
+/// Values greater than `max` are rejected with
+/// [`decode::ErrorKind::OutOfRange`]. No more bytes than the encoding of
+/// `max` occupies are read, unless the bytes read so far could start a
+/// non-minimal encoding of a value in range, see [`decode::u64_bounded`].
+pub fn read_u64_bounded<R: io::Read>(reader: R, max: u64) -> Result<u64, ReadError> {
+    read_u64_bounded_with(reader, max, Options::new())
+}
+
+/// Try to read and decode a `u64` of at most `max` from the given `Read`
+/// type using the given options.
+///
+/// See [`decode::u64_bounded_with`] for details.
+pub fn read_u64_bounded_with<R: io::Read>(mut reader: R, max: u64, options: Options) -> Result<u64, ReadError> {
+    let mut b = [0; U64_LEN];
+    let len = read_bytes(&mut reader, &mut b[.. decode::bounded_len(max, options)], 0)?;
+    match decode_read(&b[.. len], |b| decode::u64_bounded_with(b, max, options)) {
+        // The bytes so far may start a non-minimal encoding of a value in range.
+        Err(ReadError::Decode { error, .. }) if error.kind() == ErrorKind::Insufficient => {
+            let len = read_bytes(reader, &mut b[.. options.max_len(U64_LEN)], len)?;
+            decode_read(&b[.. len], |b| decode::u64_bounded_with(b, max, options))
+        }
+        result => result
+    }
+}
+
+/// Read into `b` byte by byte, starting at `from`, until a last byte has been
+/// read or `b` is full.
+///
+/// Every byte is inspected once as it is read, so there is no prefix to scan
+/// again with [`decode::peek_len`] before decoding.
+///
+/// Returns the number of bytes in `b` which have been read by this and any
+/// previous call.
+fn read_bytes<R: io::Read>(mut reader: R, b: &mut [u8], from: usize) -> Result<usize, ReadError> {
+    for i in from .. b.len() {
+        let n = reader.read(&mut b[i .. i + 1]).map_err(|e| ReadError::io(e, &b[.. i]))?;
+        if n == 0 {
+            return Err(ReadError::io(io::ErrorKind::UnexpectedEof.into(), &b[.. i]))
+        }
+        if decode::is_last(b[i]) {
+            return Ok(i + 1)
+        }
+    }
+    // Let the decoder report why `b.len()` bytes are not enough.
+    Ok(b.len())
+}
+
+/// Decode the bytes which have been read, keeping them in case of an error.
+pub(crate) fn decode_read<T, F>(bytes: &[u8], decode: F) -> Result<T, ReadError>
+where
+    F: FnOnce(&[u8]) -> Result<(T, &[u8]), decode::Error>
+{
+    match decode(bytes) {
+        Ok((n, _)) => Ok(n),
+        Err(error) => Err(ReadError::Decode { error, bytes: bytes.to_vec() })
+    }
+}
 
 macro_rules! gen {
-    ($($name:ident, $d:expr, $t:ident, $b:ident);*) => {
+    ($($name:ident, $d:expr, $t:ident);*) => {
         $(
             #[doc = " Try to read and decode a "]
             #[doc = $d]
             #[doc = " from the given `Read` type."]
-            pub fn $name<R: io::Read>(mut reader: R) -> Result<$t, ReadError> {
-                let mut b = encode::$b();
-                for i in 0 .. b.len() {
-                    let n = reader.read(&mut b[i .. i + 1])?;
-                    if n == 0 {
-                        return Err(ReadError::Io(io::ErrorKind::UnexpectedEof.into()))
-                    }
-                    if decode::is_last(b[i]) {
-                        return Ok(decode::$t(&b[..= i])?.0)
-                    }
-                }
-                Err(decode::Error::Overflow.into())
+            pub fn $name<R: io::Read>(reader: R) -> Result<$t, ReadError> {
+                read(reader)
             }
         )*
     }
 }
 
 gen! {
-    read_u8,    "`u8`",    u8,    u8_buffer;
-    read_u16,   "`u16`",   u16,   u16_buffer;
-    read_u32,   "`u32`",   u32,   u32_buffer;
-    read_u64,   "`u64`",   u64,   u64_buffer;
-    read_u128,  "`u128`",  u128,  u128_buffer;
-    read_usize, "`usize`", usize, usize_buffer
+    read_u8,    "`u8`",    u8;
+    read_u16,   "`u16`",   u16;
+    read_u32,   "`u32`",   u32;
+    read_u64,   "`u64`",   u64;
+    read_u128,  "`u128`",  u128;
+    read_usize, "`usize`", usize;
+    read_i8,    "`i8`",    i8;
+    read_i16,   "`i16`",   i16;
+    read_i32,   "`i32`",   i32;
+    read_i64,   "`i64`",   i64;
+    read_i128,  "`i128`",  i128;
+    read_isize, "`isize`", isize;
+    read_nonzero_u8,    "`NonZeroU8`",    NonZeroU8;
+    read_nonzero_u16,   "`NonZeroU16`",   NonZeroU16;
+    read_nonzero_u32,   "`NonZeroU32`",   NonZeroU32;
+    read_nonzero_u64,   "`NonZeroU64`",   NonZeroU64;
+    read_nonzero_u128,  "`NonZeroU128`",  NonZeroU128;
+    read_nonzero_usize, "`NonZeroUsize`", NonZeroUsize
 }
 
 /// Possible read errors.
+///
+/// Every error carries the bytes which have been read before it occurred,
+/// e.g. to log a corrupted stream.
 #[non_exhaustive]
 #[derive(Debug)]
 pub enum ReadError {
-    Io(io::Error),
-    Decode(decode::Error)
+    /// Reading from the underlying reader failed.
+    Io { error: io::Error, bytes: Vec<u8> },
+    /// The bytes read are not a valid varint.
+    Decode { error: decode::Error, bytes: Vec<u8> }
+}
+
+impl ReadError {
+    pub(crate) fn io(error: io::Error, bytes: &[u8]) -> Self {
+        ReadError::Io { error, bytes: bytes.to_vec() }
+    }
+
+    /// The bytes which have been read before the error occurred.
+    pub fn bytes(&self) -> &[u8] {
+        match self {
+            ReadError::Io { bytes, .. } => bytes,
+            ReadError::Decode { bytes, .. } => bytes
+        }
+    }
 }
 
 impl fmt::Display for ReadError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            ReadError::Io(e) => write!(f, "i/o error: {}", e),
-            ReadError::Decode(e) => write!(f, "decode error: {}", e)
+            ReadError::Io { error, .. } => write!(f, "i/o error: {}", error),
+            ReadError::Decode { error, .. } => write!(f, "decode error: {}", error)
         }
     }
 }
 
 impl std::error::Error for ReadError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        if let ReadError::Io(e) = self {
-            Some(e)
-        } else {
-            None
+        match self {
+            ReadError::Io { error, .. } => Some(error),
+            ReadError::Decode { error, .. } => Some(error)
         }
     }
 }
 
+/// Create an error without any bytes read.
 impl From<io::Error> for ReadError {
-    fn from(e: io::Error) -> Self {
-        ReadError::Io(e)
+    fn from(error: io::Error) -> Self {
+        ReadError::Io { error, bytes: Vec::new() }
     }
 }
 
+/// Create an error without any bytes read.
 impl From<decode::Error> for ReadError {
-    fn from(e: decode::Error) -> Self {
-        ReadError::Decode(e)
+    fn from(error: decode::Error) -> Self {
+        ReadError::Decode { error, bytes: Vec::new() }
     }
 }
 
-impl Into<io::Error> for ReadError {
-    fn into(self) -> io::Error {
-        match self {
-            ReadError::Io(e) => e,
-            ReadError::Decode(e) => e.into(),
+impl From<ReadError> for io::Error {
+    fn from(e: ReadError) -> Self {
+        match e {
+            ReadError::Io { error, .. } => error,
+            ReadError::Decode { error, .. } => error.into(),
         }
     }
 }
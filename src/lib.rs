@@ -24,9 +24,18 @@
 #![forbid(unsafe_code, unused_imports, unused_variables)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod macros;
+mod varint;
+
+pub mod big;
 pub mod decode;
 pub mod encode;
 
+pub use varint::Varint;
+
 #[cfg(feature = "std")]
 pub mod io;
 
@@ -38,3 +47,6 @@ pub mod codec;
 
 #[cfg(feature = "nom")]
 pub mod nom;
+
+#[cfg(feature = "bytes")]
+pub mod buf;
@@ -0,0 +1,65 @@
+// Copyright 2026 Parity Technologies (UK) Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS
+// OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Macros to embed encoded varints in constants.
+
+/// Encode a `u64` constant into a `[u8; N]` of exactly the encoded length at
+/// compile time.
+///
+/// The encoding is the same as the one of [`encode::u64`](crate::encode::u64).
+/// The argument must be a constant expression.
+///
+/// ```
+/// use unsigned_varint::varint;
+///
+/// const SHA2_256: [u8; 1] = varint!(0x12);
+/// static MULTICODEC: [u8; 2] = varint!(0x1220);
+/// assert_eq!([0x12], SHA2_256);
+/// assert_eq!([0xa0, 0x24], MULTICODEC);
+/// ```
+#[macro_export]
+macro_rules! varint {
+    ($number:expr) => {{
+        const NUMBER: u64 = $number;
+        const BYTES: [u8; $crate::encode::u64_len(NUMBER)] = $crate::encode::u64_array(NUMBER);
+        BYTES
+    }};
+}
+
+/// Prefix a constant byte string with the encoding of a `u64` constant into a
+/// `[u8; N]` at compile time.
+///
+/// The prefix is encoded like with [`varint!`]. Both arguments must be
+/// constant expressions.
+///
+/// ```
+/// use unsigned_varint::varint_prefixed;
+///
+/// const PROTOCOL: [u8; 6] = varint_prefixed!(0xe3, b"ipfs");
+/// assert_eq!(*b"\xe3\x01ipfs", PROTOCOL);
+/// ```
+#[macro_export]
+macro_rules! varint_prefixed {
+    ($number:expr, $bytes:expr) => {{
+        const PREFIX: &[u8] = &$crate::varint!($number);
+        const SUFFIX: &[u8] = $bytes;
+        const BYTES: [u8; PREFIX.len() + SUFFIX.len()] = $crate::encode::concat(PREFIX, SUFFIX);
+        BYTES
+    }};
+}
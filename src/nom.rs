@@ -19,32 +19,90 @@
 
 //! `nom` combinators to decode unsigned varints.
 
-use crate::decode::{self, Error};
+use crate::{decode::{self, Options}, Varint};
+use core::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize};
 use nom::{error::ErrorKind, Err as NomErr, IResult, Needed};
 
+type VarintResult<'a, T> = IResult<&'a [u8], T, (&'a [u8], ErrorKind)>;
+
+/// `nom` combinator to decode a variable-length encoded [`Varint`].
+pub fn varint<T: Varint>(input: &[u8]) -> IResult<&[u8], T, (&[u8], ErrorKind)> {
+    varint_with(Options::new())(input)
+}
+
+/// Create a `nom` combinator to decode a variable-length encoded [`Varint`]
+/// using the given options.
+pub fn varint_with<T: Varint>(options: Options) -> impl Fn(&[u8]) -> VarintResult<'_, T> {
+    move |input| {
+        let (n, remain) = T::decode_with(input, options).map_err(|err| nom_error(input, err))?;
+        Ok((remain, n))
+    }
+}
+
+/// Create a `nom` combinator to decode a variable-length encoded `u64` of at
+/// most `max`.
+///
+/// See [`decode::u64_bounded`] for details.
+pub fn u64_bounded(max: u64) -> impl Fn(&[u8]) -> VarintResult<'_, u64> {
+    u64_bounded_with(max, Options::new())
+}
+
+/// Create a `nom` combinator to decode a variable-length encoded `u64` of at
+/// most `max` using the given options.
+///
+/// See [`decode::u64_bounded_with`] for details.
+pub fn u64_bounded_with(max: u64, options: Options) -> impl Fn(&[u8]) -> VarintResult<'_, u64> {
+    move |input| {
+        let (n, remain) = decode::u64_bounded_with(input, max, options).map_err(|err| nom_error(input, err))?;
+        Ok((remain, n))
+    }
+}
+
+fn nom_error(input: &[u8], err: decode::Error) -> NomErr<(&[u8], ErrorKind)> {
+    match err.kind() {
+        decode::ErrorKind::Insufficient => {
+            NomErr::Incomplete(err.needed().map_or(Needed::Unknown, Needed::new))
+        }
+        decode::ErrorKind::Overflow => NomErr::Error((input, ErrorKind::TooLarge)),
+        decode::ErrorKind::NotMinimal => NomErr::Error((input, ErrorKind::Verify)),
+        decode::ErrorKind::ExcessBits => NomErr::Error((input, ErrorKind::TooLarge)),
+        decode::ErrorKind::ExceedsSpecLimit => NomErr::Error((input, ErrorKind::TooLarge)),
+        decode::ErrorKind::Zero => NomErr::Error((input, ErrorKind::Verify)),
+        decode::ErrorKind::OutOfRange => NomErr::Error((input, ErrorKind::TooLarge)),
+        decode::ErrorKind::Ambiguous => NomErr::Error((input, ErrorKind::Verify)),
+    }
+}
+
 macro_rules! gen {
-    ($($type:ident, $d:expr);*) => {
+    ($($name:ident, $type:ident, $d:expr);*) => {
         $(
             #[doc = " `nom` combinator to decode a variable-length encoded "]
             #[doc = $d]
             #[doc = "."]
-            pub fn $type(input: &[u8]) -> IResult<&[u8], $type, (&[u8], ErrorKind)> {
-                let (n, remain) = decode::$type(input).map_err(|err| match err {
-                    Error::Insufficient => NomErr::Incomplete(Needed::Unknown),
-                    Error::Overflow => NomErr::Error((input, ErrorKind::TooLarge)),
-                    Error::NotMinimal => NomErr::Error((input, ErrorKind::Verify)),
-                })?;
-                Ok((remain, n))
+            pub fn $name(input: &[u8]) -> IResult<&[u8], $type, (&[u8], ErrorKind)> {
+                varint(input)
             }
         )*
     }
 }
 
 gen! {
-    u8,    "`u8`";
-    u16,   "`u16`";
-    u32,   "`u32`";
-    u64,   "`u64`";
-    u128,  "`u128`";
-    usize, "`usize`"
+    u8,            u8,           "`u8`";
+    u16,           u16,          "`u16`";
+    u32,           u32,          "`u32`";
+    u64,           u64,          "`u64`";
+    u128,          u128,         "`u128`";
+    usize,         usize,        "`usize`";
+    i8,            i8,           "ZigZag-encoded `i8`";
+    i16,           i16,          "ZigZag-encoded `i16`";
+    i32,           i32,          "ZigZag-encoded `i32`";
+    i64,           i64,          "ZigZag-encoded `i64`";
+    i128,          i128,         "ZigZag-encoded `i128`";
+    isize,         isize,        "ZigZag-encoded `isize`";
+    nonzero_u8,    NonZeroU8,    "`NonZeroU8`";
+    nonzero_u16,   NonZeroU16,   "`NonZeroU16`";
+    nonzero_u32,   NonZeroU32,   "`NonZeroU32`";
+    nonzero_u64,   NonZeroU64,   "`NonZeroU64`";
+    nonzero_u128,  NonZeroU128,  "`NonZeroU128`";
+    nonzero_usize, NonZeroUsize, "`NonZeroUsize`"
 }
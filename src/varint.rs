@@ -0,0 +1,137 @@
+// Copyright 2026 Parity Technologies (UK) Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS
+// OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! The [`Varint`] trait.
+
+use crate::{decode, encode};
+use core::{
+    convert::TryFrom,
+    num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize}
+};
+
+/// An integer type which can be encoded as and decoded from a varint.
+///
+/// This trait is implemented for all integer types supported by this crate
+/// and allows writing code which is generic over them. It is sealed, i.e. it
+/// can not be implemented outside of this crate.
+pub trait Varint: Sized + sealed::Sealed {
+    /// The maximum number of bytes an encoded value of this type occupies.
+    const MAX_LEN: usize;
+
+    /// An array buffer large enough to hold any encoded value of this type.
+    type Buffer: AsRef<[u8]> + AsMut<[u8]> + Default;
+
+    /// Return the number of bytes the encoding of this value occupies.
+    fn encoded_len(&self) -> usize;
+
+    /// Encode this value into the given buffer.
+    ///
+    /// Returns the slice of encoded bytes.
+    fn encode_into(self, buf: &mut Self::Buffer) -> &[u8];
+
+    /// Decode a value of this type from the given slice.
+    ///
+    /// Returns the value and the remaining slice.
+    fn decode_from(buf: &[u8]) -> Result<(Self, &[u8]), decode::Error>;
+
+    /// Decode a value of this type from the given slice using the given options.
+    ///
+    /// Returns the value and the remaining slice.
+    fn decode_with(buf: &[u8], options: decode::Options) -> Result<(Self, &[u8]), decode::Error>;
+}
+
+pub(crate) mod sealed {
+    pub trait Sealed: Sized {
+        /// The number of bits of the (unsigned) type.
+        const BITS: u32;
+
+        /// Is zero an invalid value?
+        const NONZERO: bool;
+
+        /// Convert the value of a minimally encoded varint of at most 8 bytes.
+        ///
+        /// Returns `None` if the value is invalid for this type.
+        fn from_word(word: u64) -> Option<Self>;
+    }
+}
+
+macro_rules! impl_varint {
+    ($($t:ident, $len:ident, $f:ident, $w:ident, $from:expr);*) => {
+        $(impl_varint!(@impl $t, $len, $f, $t, $w, false, $from);)*
+    };
+    (@impl $t:ident, $len:ident, $f:ident, $name:ident, $w:ident, $nonzero:expr, $from:expr) => {
+        impl sealed::Sealed for $t {
+            const BITS: u32 = $t::BITS;
+            const NONZERO: bool = $nonzero;
+
+            #[inline]
+            fn from_word(word: u64) -> Option<Self> {
+                $from(word)
+            }
+        }
+
+        impl Varint for $t {
+            const MAX_LEN: usize = encode::$len;
+
+            type Buffer = [u8; encode::$len];
+
+            #[inline]
+            fn encoded_len(&self) -> usize {
+                encode::$f(*self)
+            }
+
+            #[inline]
+            fn encode_into(self, buf: &mut Self::Buffer) -> &[u8] {
+                encode::$name(self, buf)
+            }
+
+            #[inline]
+            fn decode_from(buf: &[u8]) -> Result<(Self, &[u8]), decode::Error> {
+                decode::$name(buf)
+            }
+
+            #[inline]
+            fn decode_with(buf: &[u8], options: decode::Options) -> Result<(Self, &[u8]), decode::Error> {
+                decode::$w(buf, options)
+            }
+        }
+    }
+}
+
+impl_varint! {
+    u8,    U8_LEN,    u8_len,    u8_with,    |w| u8::try_from(w).ok();
+    u16,   U16_LEN,   u16_len,   u16_with,   |w| u16::try_from(w).ok();
+    u32,   U32_LEN,   u32_len,   u32_with,   |w| u32::try_from(w).ok();
+    u64,   U64_LEN,   u64_len,   u64_with,   Some;
+    u128,  U128_LEN,  u128_len,  u128_with,  |w| Some(u128::from(w));
+    usize, USIZE_LEN, usize_len, usize_with, |w| usize::try_from(w).ok();
+    i8,    I8_LEN,    i8_len,    i8_with,    |w| u8::try_from(w).ok().map(|n| (n >> 1) as i8 ^ -((n & 1) as i8));
+    i16,   I16_LEN,   i16_len,   i16_with,   |w| u16::try_from(w).ok().map(|n| (n >> 1) as i16 ^ -((n & 1) as i16));
+    i32,   I32_LEN,   i32_len,   i32_with,   |w| u32::try_from(w).ok().map(|n| (n >> 1) as i32 ^ -((n & 1) as i32));
+    i64,   I64_LEN,   i64_len,   i64_with,   |n: u64| Some((n >> 1) as i64 ^ -((n & 1) as i64));
+    i128,  I128_LEN,  i128_len,  i128_with,  |n: u64| Some(i128::from((n >> 1) as i64 ^ -((n & 1) as i64)));
+    isize, ISIZE_LEN, isize_len, isize_with, |w| usize::try_from(w).ok().map(|n| (n >> 1) as isize ^ -((n & 1) as isize))
+}
+
+impl_varint!(@impl NonZeroU8,    U8_LEN,    nonzero_u8_len,    nonzero_u8,    nonzero_u8_with,    true, |w| u8::try_from(w).ok().and_then(NonZeroU8::new));
+impl_varint!(@impl NonZeroU16,   U16_LEN,   nonzero_u16_len,   nonzero_u16,   nonzero_u16_with,   true, |w| u16::try_from(w).ok().and_then(NonZeroU16::new));
+impl_varint!(@impl NonZeroU32,   U32_LEN,   nonzero_u32_len,   nonzero_u32,   nonzero_u32_with,   true, |w| u32::try_from(w).ok().and_then(NonZeroU32::new));
+impl_varint!(@impl NonZeroU64,   U64_LEN,   nonzero_u64_len,   nonzero_u64,   nonzero_u64_with,   true, NonZeroU64::new);
+impl_varint!(@impl NonZeroU128,  U128_LEN,  nonzero_u128_len,  nonzero_u128,  nonzero_u128_with,  true, |w| NonZeroU128::new(u128::from(w)));
+impl_varint!(@impl NonZeroUsize, USIZE_LEN, nonzero_usize_len, nonzero_usize, nonzero_usize_with, true, |w| usize::try_from(w).ok().and_then(NonZeroUsize::new));
@@ -0,0 +1,166 @@
+// Copyright 2026 Parity Technologies (UK) Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS
+// OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+use quickcheck::QuickCheck;
+use unsigned_varint::{big, decode::{self, ErrorKind, Options}, encode};
+
+#[test]
+fn identity_u128() {
+    fn prop(n: u128) -> bool {
+        let mut expected = encode::u128_buffer();
+        let expected = encode::u128(n, &mut expected);
+        let mut buf = [0xaa; 24];
+        let len = big::encode(&n.to_le_bytes(), &mut buf).unwrap();
+        let limbs = [n as u64, (n >> 64) as u64];
+        let mut lbuf = [0; 19];
+        let llen = big::encode_limbs(&limbs, &mut lbuf).unwrap();
+        let mut out = [0xff; 16];
+        let rest = big::decode(&buf, &mut out).unwrap();
+        buf[.. len] == *expected
+            && lbuf[.. llen] == *expected
+            && big::encoded_len(&n.to_le_bytes()) == len
+            && big::encoded_len_limbs(&limbs) == len
+            && rest.len() == 24 - len
+            && u128::from_le_bytes(out) == n
+            && big::decode_limbs::<2>(expected) == Ok((limbs, &[][..]))
+    }
+    QuickCheck::new()
+        .tests(10_000)
+        .quickcheck(prop as fn(u128) -> bool)
+}
+
+#[test]
+fn widths() {
+    // Values beyond the width of the output.
+    let mut out = [0; 2];
+    let bytes = [0xff, 0xff, 0x03];
+    assert_eq!(Ok(&[][..]), big::decode(&bytes, &mut out));
+    assert_eq!([0xff, 0xff], out);
+    let err = big::decode(&[0xff, 0xff, 0x04], &mut out).unwrap_err();
+    assert_eq!((ErrorKind::ExcessBits, 2), (err.kind(), err.offset()));
+    let truncate = Options::new().with_reject_excess_bits(false);
+    assert_eq!(Ok(&[][..]), big::decode_with(&[0xff, 0xff, 0x07], &mut out, truncate));
+    assert_eq!([0xff, 0xff], out);
+    assert_eq!(ErrorKind::Overflow, big::decode(&[0x80, 0x80, 0x80, 1], &mut out).unwrap_err().kind());
+    assert_eq!(ErrorKind::NotMinimal, big::decode(&[0x80, 0], &mut out).unwrap_err().kind());
+    assert_eq!(ErrorKind::Insufficient, big::decode(&[0x80], &mut out).unwrap_err().kind());
+
+    // Zero width.
+    assert_eq!(Ok(&[][..]), big::decode(&[0], &mut []));
+    assert_eq!(ErrorKind::ExcessBits, big::decode(&[1], &mut []).unwrap_err().kind());
+    assert_eq!(Ok(1), big::encode(&[], &mut [0xff]));
+
+    // 256 bits.
+    let max = [u64::MAX; 4];
+    let mut buf = [0; 40];
+    assert_eq!(Ok(37), big::encode_limbs(&max, &mut buf));
+    assert_eq!(Ok((max, &buf[37 ..])), big::decode_limbs::<4>(&buf));
+    assert_eq!(
+        Err(encode::Error::BufferTooSmall { required: 37 }),
+        big::encode_limbs(&max, &mut [0; 36])
+    );
+    assert_eq!(ErrorKind::ExcessBits, big::decode_limbs::<3>(&buf).unwrap_err().kind());
+    assert_eq!(Some(37), decode::encoded_len(&buf))
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn identity_vec() {
+    fn prop(number: Vec<u8>, tail: Vec<u8>) -> bool {
+        let mut bytes = Vec::new();
+        big::encode_vec(&number, &mut bytes);
+        let len = bytes.len();
+        bytes.extend_from_slice(&tail);
+        let mut trimmed = number.clone();
+        while trimmed.last() == Some(&0) {
+            trimmed.pop();
+        }
+        let limbs = number.iter().map(|b| u64::from(*b)).collect::<Vec<_>>();
+        let mut limb_bytes = Vec::new();
+        big::encode_limbs_vec(&limbs, &mut limb_bytes);
+        len == big::encoded_len(&number)
+            && big::decode_vec(&bytes, len) == Ok((trimmed, &tail[..]))
+            && big::decode_vec(&limb_bytes, usize::MAX).map(|r| r.1.is_empty()) == Ok(true)
+    }
+    QuickCheck::new()
+        .tests(10_000)
+        .quickcheck(prop as fn(Vec<u8>, Vec<u8>) -> bool)
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_limit() {
+    let mut bytes = Vec::new();
+    big::encode_vec(&[0xff; 64], &mut bytes);
+    assert_eq!(74, bytes.len());
+    assert_eq!(Ok((vec![0xff; 64], &[][..])), big::decode_vec(&bytes, 74));
+    let err = big::decode_vec(&bytes, 73).unwrap_err();
+    assert_eq!((ErrorKind::Overflow, 72, 73), (err.kind(), err.offset(), err.examined()));
+    let err = big::decode_vec(&bytes[.. 10], 73).unwrap_err();
+    assert_eq!((ErrorKind::Insufficient, 10), (err.kind(), err.offset()));
+    // Huge limits do not allocate up front.
+    assert_eq!(Ok((vec![], &[][..])), big::decode_vec(&[0], usize::MAX));
+    assert_eq!(ErrorKind::NotMinimal, big::decode_vec(&[0x81, 0], 8).unwrap_err().kind())
+}
+
+#[cfg(feature = "num-bigint")]
+#[test]
+fn identity_biguint() {
+    use num_bigint::BigUint;
+
+    fn prop(digits: Vec<u32>) -> bool {
+        let n = BigUint::new(digits);
+        let mut bytes = Vec::new();
+        big::encode_biguint(&n, &mut bytes);
+        big::decode_biguint(&bytes, bytes.len()) == Ok((n, &[][..]))
+    }
+    QuickCheck::new()
+        .tests(10_000)
+        .quickcheck(prop as fn(Vec<u32>) -> bool);
+
+    let n = BigUint::from(300u32);
+    let mut bytes = Vec::new();
+    big::encode_biguint(&n, &mut bytes);
+    assert_eq!(vec![0xac, 0x02], bytes);
+    assert!(big::decode_biguint(&[0x80, 0x80, 1], 2).is_err())
+}
+
+#[cfg(feature = "primitive-types")]
+#[test]
+fn identity_primitive_types() {
+    use primitive_types::{U256, U512};
+
+    fn prop(a: [u64; 4], b: [u64; 8]) -> bool {
+        let mut buf = [0; big::U256_LEN];
+        let mut buf512 = [0; big::U512_LEN];
+        big::decode_u256(big::encode_u256(U256(a), &mut buf)) == Ok((U256(a), &[][..]))
+            && big::decode_u512(big::encode_u512(U512(b), &mut buf512)) == Ok((U512(b), &[][..]))
+    }
+    QuickCheck::new()
+        .tests(10_000)
+        .quickcheck(prop as fn([u64; 4], [u64; 8]) -> bool);
+
+    let mut buf = [0; big::U256_LEN];
+    assert_eq!(&[0xac, 0x02], big::encode_u256(U256::from(300), &mut buf));
+    assert_eq!(big::U256_LEN, big::encode_u256(U256::MAX, &mut buf).len());
+    let mut buf = [0; big::U512_LEN];
+    assert_eq!(big::U512_LEN, big::encode_u512(U512::MAX, &mut buf).len());
+    let options = Options::new().with_max_bytes(2);
+    assert_eq!(ErrorKind::Overflow, big::decode_u256_with(&[0x80, 0x80, 1], options).unwrap_err().kind())
+}
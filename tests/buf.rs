@@ -0,0 +1,111 @@
+// Copyright 2026 Parity Technologies (UK) Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS
+// OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+#![cfg(feature = "bytes")]
+
+use bytes::Buf;
+use quickcheck::QuickCheck;
+use unsigned_varint::{buf::{BufExt, BufMutExt}, decode::ErrorKind, encode};
+
+#[test]
+fn get_across_chunks() {
+    fn prop(xs: Vec<u64>, ys: Vec<i32>, split: usize) -> bool {
+        let mut bytes = Vec::new();
+        for (x, y) in xs.iter().zip(&ys) {
+            bytes.put_uvarint_u64(*x);
+            bytes.put_uvarint_i32(*y)
+        }
+        let split = if bytes.is_empty() { 0 } else { split % bytes.len() };
+        let (a, b) = bytes.split_at(split);
+        let mut buf = a.chain(b);
+        for (x, y) in xs.iter().zip(&ys) {
+            if buf.get_uvarint_u64() != Ok(*x) || buf.get_uvarint_i32() != Ok(*y) {
+                return false
+            }
+        }
+        !buf.has_remaining()
+    }
+    QuickCheck::new()
+        .tests(10_000)
+        .quickcheck(prop as fn(Vec<u64>, Vec<i32>, usize) -> bool)
+}
+
+#[test]
+fn get_byte_by_byte() {
+    let bytes = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 1, 7];
+    let mut buf = bytes[.. 3]
+        .chain(&bytes[3 .. 4])
+        .chain(&bytes[4 .. 9])
+        .chain(&bytes[9 ..]);
+    assert_eq!(Ok(u64::MAX), buf.get_uvarint());
+    assert_eq!(Ok(7u8), buf.get_uvarint());
+    assert!(!buf.has_remaining())
+}
+
+#[test]
+fn get_consumes_examined_bytes() {
+    // Not minimal, within one chunk.
+    let mut buf = &[0x81, 0, 5][..];
+    let err = buf.get_uvarint_u32().unwrap_err();
+    assert_eq!((ErrorKind::NotMinimal, 2), (err.kind(), err.examined()));
+    assert_eq!(&[5][..], buf);
+
+    // Not minimal, across chunks.
+    let mut buf = (&[0x81][..]).chain(&[0, 5][..]);
+    let err = buf.get_uvarint_u32().unwrap_err();
+    assert_eq!((ErrorKind::NotMinimal, 1, 2), (err.kind(), err.offset(), err.examined()));
+    assert_eq!(1, buf.remaining());
+
+    // Too many bytes.
+    let mut buf = (&[0x80, 0x80][..]).chain(&[0x80, 0x80][..]);
+    let err = buf.get_uvarint_u8().unwrap_err();
+    assert_eq!((ErrorKind::Overflow, 2), (err.kind(), err.examined()));
+    assert_eq!(2, buf.remaining());
+
+    // Insufficient input consumes everything.
+    let mut buf = (&[0x80][..]).chain(&[0x80][..]);
+    let err = buf.get_uvarint_u64().unwrap_err();
+    assert_eq!((ErrorKind::Insufficient, Some(1)), (err.kind(), err.needed()));
+    assert!(!buf.has_remaining());
+    assert_eq!(ErrorKind::Insufficient, (&[][..]).get_uvarint_u8().unwrap_err().kind())
+}
+
+#[test]
+fn put() {
+    fn prop(n: u128, m: i64) -> bool {
+        let mut bytes = bytes::BytesMut::new();
+        bytes.put_uvarint(n);
+        bytes.put_uvarint_i64(m);
+        let mut a = encode::u128_buffer();
+        let mut b = encode::i64_buffer();
+        let mut expected = encode::u128(n, &mut a).to_vec();
+        expected.extend_from_slice(encode::i64(m, &mut b));
+        bytes[..] == expected[..]
+    }
+    QuickCheck::new()
+        .tests(10_000)
+        .quickcheck(prop as fn(u128, i64) -> bool)
+}
+
+#[test]
+#[should_panic]
+fn put_without_capacity() {
+    let mut buf = [0u8; 2];
+    (&mut buf[..]).put_uvarint_u32(1 << 14)
+}
@@ -17,6 +17,8 @@
 // WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
+#![cfg(any(feature = "std", feature = "nom"))]
+
 use quickcheck::{Arbitrary, Gen};
 use unsigned_varint::encode;
 
@@ -33,12 +35,261 @@ fn read_arbitrary() {
             RandomUvi::U32(n, _)   => assert_eq!(n, io::read_u32(&mut r).unwrap()),
             RandomUvi::U64(n, _)   => assert_eq!(n, io::read_u64(&mut r).unwrap()),
             RandomUvi::U128(n, _)  => assert_eq!(n, io::read_u128(&mut r).unwrap()),
-            RandomUvi::Usize(n, _) => assert_eq!(n, io::read_usize(&mut r).unwrap())
+            RandomUvi::Usize(n, _) => assert_eq!(n, io::read_usize(&mut r).unwrap()),
+            RandomUvi::I64(n, _)   => assert_eq!(n, io::read_i64(&mut r).unwrap()),
+            RandomUvi::I128(n, _)  => assert_eq!(n, io::read_i128(&mut r).unwrap())
         }
     }
     quickcheck::quickcheck(property as fn(RandomUvi))
 }
 
+#[cfg(feature = "std")]
+#[test]
+fn read_generic() {
+    use unsigned_varint::{io, Varint};
+
+    fn read_len<T: Varint>(bytes: &[u8]) -> T {
+        io::read(std::io::Cursor::new(bytes)).unwrap()
+    }
+    assert_eq!(300u16, read_len(&[0xAC, 0x02]));
+    assert_eq!(-2i64, read_len(&[0x03]));
+    assert!(io::read::<u8, _>(std::io::Cursor::new(&[0x80, 0x80, 1])).is_err())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn read_with_options() {
+    use unsigned_varint::{decode::{ErrorKind, Options}, io::{self, ReadError}};
+
+    // Reading stops after the maximum number of bytes.
+    let mut r = std::io::Cursor::new(&[0x80, 0x80, 0x80, 1][..]);
+    match io::read_with::<u64, _>(&mut r, Options::new().with_max_bytes(2)) {
+        Err(ReadError::Decode { error, bytes }) => {
+            assert_eq!(ErrorKind::Overflow, error.kind());
+            assert_eq!(&[0x80, 0x80][..], &bytes[..]);
+            assert_eq!(2, r.position())
+        }
+        other => panic!("unexpected result: {:?}", other)
+    }
+    let mut r = std::io::Cursor::new(&[0x81, 0x80, 0][..]);
+    assert_eq!(1, io::read_with::<u32, _>(&mut r, Options::new().with_require_minimal(false)).unwrap());
+
+    // Errors carry the bytes read so far.
+    let err = io::read::<u32, _>(std::io::Cursor::new(&[0x81, 0x80, 0][..])).unwrap_err();
+    assert!(matches!(err, ReadError::Decode { .. }));
+    assert_eq!(&[0x81, 0x80, 0][..], err.bytes());
+    let err = io::read::<u32, _>(std::io::Cursor::new(&[0xff, 0xff, 0xff, 0xff, 0x1f, 1][..])).unwrap_err();
+    assert_eq!(5, err.bytes().len());
+    match err {
+        ReadError::Decode { error, .. } => assert_eq!(ErrorKind::ExcessBits, error.kind()),
+        other => panic!("unexpected error: {:?}", other)
+    }
+    let err = io::read::<u64, _>(std::io::Cursor::new(&[0xac][..])).unwrap_err();
+    assert!(matches!(err, ReadError::Io { .. }));
+    assert_eq!(&[0xac][..], err.bytes())
+}
+
+#[cfg(feature = "futures")]
+#[test]
+fn async_read_with_options() {
+    use unsigned_varint::{aio, decode::{ErrorKind, Options}, io::ReadError};
+
+    futures_executor::block_on(async {
+        let mut r = futures_util::io::Cursor::new(&[0x80, 0x80, 0x80, 1][..]);
+        match aio::read_with::<u64, _>(&mut r, Options::new().with_max_bytes(2)).await {
+            Err(ReadError::Decode { error, bytes }) => {
+                assert_eq!(ErrorKind::Overflow, error.kind());
+                assert_eq!(&[0x80, 0x80][..], &bytes[..]);
+                assert_eq!(2, r.position())
+            }
+            other => panic!("unexpected result: {:?}", other)
+        }
+        let mut r = futures_util::io::Cursor::new(&[0x80, 0x81][..]);
+        let err = aio::read::<u64, _>(&mut r).await.unwrap_err();
+        assert!(matches!(err, ReadError::Io { .. }));
+        assert_eq!(&[0x80, 0x81][..], err.bytes())
+    })
+}
+
+#[cfg(feature = "nom")]
+#[test]
+fn nom_with_options() {
+    use unsigned_varint::{decode::Options, nom};
+
+    let parser = nom::varint_with::<u16>(Options::new().with_require_minimal(false));
+    assert_eq!(Ok((&[7][..], 1)), parser(&[0x81, 0x80, 0, 7]));
+    assert!(nom::u16(&[0x81, 0x80, 0, 7]).is_err())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn read_bounded() {
+    use unsigned_varint::{decode::ErrorKind, io::{self, ReadError}};
+
+    let mut r = std::io::Cursor::new(&[0xff; 10][..]);
+    match io::read_u64_bounded(&mut r, 31) {
+        Err(ReadError::Decode { error, bytes }) => {
+            assert_eq!(ErrorKind::OutOfRange, error.kind());
+            assert_eq!(vec![0xff], bytes);
+            assert_eq!(1, r.position())
+        }
+        other => panic!("unexpected result: {:?}", other)
+    }
+    assert_eq!(300, io::read_u64_bounded(&[0xac, 2][..], 300).unwrap());
+    let mut r = std::io::Cursor::new(&[0x80, 0, 7][..]);
+    match io::read_u64_bounded(&mut r, 127) {
+        Err(ReadError::Decode { error, bytes }) => {
+            assert_eq!(ErrorKind::NotMinimal, error.kind());
+            assert_eq!(vec![0x80, 0], bytes);
+            assert_eq!(2, r.position())
+        }
+        other => panic!("unexpected result: {:?}", other)
+    }
+    #[cfg(feature = "futures")]
+    futures_executor::block_on(async {
+        use unsigned_varint::aio;
+        let mut r = futures_util::io::Cursor::new(&[0x80, 0x80, 0x80, 0x80, 8][..]);
+        let err = aio::read_u64_bounded(&mut r, (1 << 31) - 1).await.unwrap_err();
+        assert_eq!(5, r.position());
+        assert_eq!(&[0x80, 0x80, 0x80, 0x80, 8][..], err.bytes());
+        assert_eq!(5, aio::read_u64_bounded(&[5][..], 5).await.unwrap());
+        let err = aio::read_u64_bounded(&[0x80, 0x80, 1][..], 127).await.unwrap_err();
+        assert_eq!(&[0x80, 0x80, 1][..], err.bytes());
+        assert!(matches!(err, ReadError::Decode { error, .. } if error.kind() == ErrorKind::OutOfRange))
+    })
+}
+
+#[cfg(feature = "nom")]
+#[test]
+fn nom_bounded() {
+    use unsigned_varint::nom;
+
+    assert_eq!(Ok((&[7][..], 31)), nom::u64_bounded(31)(&[31, 7]));
+    assert_eq!(Err(::nom::Err::Error((&[32][..], ::nom::error::ErrorKind::TooLarge))), nom::u64_bounded(31)(&[32]));
+    assert!(matches!(nom::u64_bounded(1000)(&[0x80]), Err(::nom::Err::Incomplete(_))));
+    assert!(matches!(nom::u64_bounded(127)(&[0x80, 0x80]), Err(::nom::Err::Incomplete(_))))
+}
+
+#[cfg(feature = "codec")]
+#[test]
+fn codec_with_options() {
+    use bytes::BytesMut;
+    use tokio_util::codec::Decoder;
+    use unsigned_varint::{codec::{Uvi, UviBytes}, decode::Options};
+
+    let mut uvi = Uvi::<u32>::default();
+    uvi.set_options(Options::new().with_max_bytes(1));
+    assert!(uvi.decode(&mut BytesMut::from(&[0x80, 1][..])).is_err());
+    assert_eq!(Some(5), uvi.decode(&mut BytesMut::from(&[5][..])).unwrap());
+
+    let mut uvi_bytes = UviBytes::<bytes::Bytes>::default();
+    uvi_bytes.set_options(Options::new().with_require_minimal(false));
+    let mut src = BytesMut::from(&[0x82, 0, 1, 2, 3][..]);
+    assert_eq!(&[1, 2][..], &uvi_bytes.decode(&mut src).unwrap().unwrap()[..]);
+    assert_eq!(&[3][..], &src[..])
+}
+
+#[cfg(feature = "codec")]
+#[test]
+fn codec_partial() {
+    use bytes::BytesMut;
+    use tokio_util::codec::Decoder;
+    use unsigned_varint::{codec::{Uvi, UviBytes}, decode::Options};
+
+    let mut uvi = Uvi::<u64>::default();
+    let mut src = BytesMut::new();
+    for b in [0xff, 0xff, 0x03] {
+        assert_eq!(None, uvi.decode(&mut src).unwrap());
+        src.extend_from_slice(&[b])
+    }
+    assert_eq!(Some(0xffff), uvi.decode(&mut src).unwrap());
+    assert!(src.is_empty());
+    assert!(uvi.decode(&mut BytesMut::from(&[0x80; 10][..])).is_err());
+
+    // Polling again after a partial varint only inspects the new bytes.
+    let mut src = BytesMut::new();
+    for _ in 0 .. 9 {
+        src.extend_from_slice(&[0x80]);
+        assert_eq!(None, uvi.decode(&mut src).unwrap())
+    }
+    src.extend_from_slice(&[0x80, 1]);
+    assert!(uvi.decode(&mut src).is_err());
+    src.clear();
+    src.extend_from_slice(&[0x81]);
+    assert_eq!(None, uvi.decode(&mut src).unwrap());
+    src.extend_from_slice(&[1, 5]);
+    assert_eq!(Some(129), uvi.decode(&mut src).unwrap());
+    assert_eq!(&[5][..], &src[..]);
+
+    // A partial varint is forgotten when the buffer is replaced ...
+    assert_eq!(None, uvi.decode(&mut BytesMut::from(&[0x80, 0x80, 0x80][..])).unwrap());
+    assert_eq!(Some(5), uvi.decode(&mut BytesMut::from(&[5][..])).unwrap());
+
+    // ... or the options are changed.
+    assert_eq!(None, uvi.decode(&mut BytesMut::from(&[0x80, 0x80, 0x80][..])).unwrap());
+    uvi.set_options(Options::new().with_max_bytes(2));
+    assert_eq!(Some(5), uvi.decode(&mut BytesMut::from(&[5][..])).unwrap());
+    assert!(uvi.decode(&mut BytesMut::from(&[0x80, 0x80, 1][..])).is_err());
+
+    let mut uvi_bytes = UviBytes::<bytes::Bytes>::default();
+    let mut src = BytesMut::from(&[0x81][..]);
+    assert_eq!(None, uvi_bytes.decode(&mut src).unwrap());
+    src.extend_from_slice(&[1, 7]);
+    assert_eq!(None, uvi_bytes.decode(&mut src).unwrap());
+    src.extend_from_slice(&[0; 128]);
+    assert_eq!(129, uvi_bytes.decode(&mut src).unwrap().unwrap().len());
+    assert_eq!(None, uvi_bytes.decode(&mut BytesMut::from(&[0x80, 0x80][..])).unwrap());
+    uvi_bytes.set_options(Options::new().with_max_bytes(1));
+    assert_eq!(&[7][..], &uvi_bytes.decode(&mut BytesMut::from(&[1, 7][..])).unwrap().unwrap()[..])
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn read_nonzero() {
+    use std::num::NonZeroU32;
+    use unsigned_varint::{decode::ErrorKind, io::{self, ReadError}};
+
+    assert_eq!(NonZeroU32::new(300), io::read_nonzero_u32(&[0xac, 2][..]).ok());
+    match io::read_nonzero_u32(&[0][..]) {
+        Err(ReadError::Decode { error, bytes }) => {
+            assert_eq!(ErrorKind::Zero, error.kind());
+            assert_eq!(vec![0], bytes)
+        }
+        other => panic!("unexpected result: {:?}", other)
+    }
+    #[cfg(feature = "futures")]
+    futures_executor::block_on(async {
+        use unsigned_varint::aio;
+        assert_eq!(NonZeroU32::new(5), aio::read_nonzero_u32(&[5][..]).await.ok());
+        assert!(aio::read_nonzero_u32(&[0][..]).await.is_err())
+    })
+}
+
+#[cfg(feature = "nom")]
+#[test]
+fn nom_nonzero() {
+    use unsigned_varint::nom;
+
+    assert_eq!(Ok((&[7][..], std::num::NonZeroU16::new(1).unwrap())), nom::nonzero_u16(&[1, 7]));
+    assert!(nom::nonzero_u16(&[0, 7]).is_err())
+}
+
+#[cfg(feature = "codec")]
+#[test]
+fn codec_nonzero() {
+    use bytes::BytesMut;
+    use std::num::NonZeroU64;
+    use tokio_util::codec::{Decoder, Encoder};
+    use unsigned_varint::codec::Uvi;
+
+    let mut uvi = Uvi::<NonZeroU64>::default();
+    let mut dst = BytesMut::new();
+    uvi.encode(NonZeroU64::new(300).unwrap(), &mut dst).unwrap();
+    assert_eq!(&[0xac, 2][..], &dst[..]);
+    assert_eq!(NonZeroU64::new(300), uvi.decode(&mut dst).unwrap());
+    assert!(uvi.decode(&mut BytesMut::from(&[0][..])).is_err())
+}
+
 #[cfg(feature = "futures")]
 #[test]
 fn async_read_arbitrary() {
@@ -53,7 +304,9 @@ fn async_read_arbitrary() {
                 RandomUvi::U32(n, _)   => assert_eq!(n, aio::read_u32(&mut r).await.unwrap()),
                 RandomUvi::U64(n, _)   => assert_eq!(n, aio::read_u64(&mut r).await.unwrap()),
                 RandomUvi::U128(n, _)  => assert_eq!(n, aio::read_u128(&mut r).await.unwrap()),
-                RandomUvi::Usize(n, _) => assert_eq!(n, aio::read_usize(&mut r).await.unwrap())
+                RandomUvi::Usize(n, _) => assert_eq!(n, aio::read_usize(&mut r).await.unwrap()),
+                RandomUvi::I64(n, _)   => assert_eq!(n, aio::read_i64(&mut r).await.unwrap()),
+                RandomUvi::I128(n, _)  => assert_eq!(n, aio::read_i128(&mut r).await.unwrap())
             }
         })
     }
@@ -76,6 +329,8 @@ fn nom_read_arbitrary() {
             RandomUvi::U64(n, _)   => assert_eq!((empty, n), nom::u64(input).unwrap()),
             RandomUvi::U128(n, _)  => assert_eq!((empty, n), nom::u128(input).unwrap()),
             RandomUvi::Usize(n, _) => assert_eq!((empty, n), nom::usize(input).unwrap()),
+            RandomUvi::I64(n, _)   => assert_eq!((empty, n), nom::i64(input).unwrap()),
+            RandomUvi::I128(n, _)  => assert_eq!((empty, n), nom::i128(input).unwrap()),
         }
     }
     quickcheck::quickcheck(property as fn(RandomUvi))
@@ -89,10 +344,11 @@ enum RandomUvi {
     U64(u64, Vec<u8>),
     U128(u128, Vec<u8>),
     Usize(usize, Vec<u8>),
+    I64(i64, Vec<u8>),
+    I128(i128, Vec<u8>),
 }
 
 impl RandomUvi {
-    #[cfg(feature = "std")]
     fn bytes(&self) -> &[u8] {
         match self {
             RandomUvi::U8(_,  v) => v,
@@ -100,7 +356,9 @@ impl RandomUvi {
             RandomUvi::U32(_, v) => v,
             RandomUvi::U64(_, v) => v,
             RandomUvi::U128(_, v) => v,
-            RandomUvi::Usize(_, v) => v
+            RandomUvi::Usize(_, v) => v,
+            RandomUvi::I64(_, v) => v,
+            RandomUvi::I128(_, v) => v
         }
     }
 }
@@ -108,7 +366,7 @@ impl RandomUvi {
 impl Arbitrary for RandomUvi {
     fn arbitrary(g: &mut Gen) -> Self {
         let n: u128 = Arbitrary::arbitrary(g);
-        match n % 6 {
+        match n % 8 {
             0 => {
                 let mut b = encode::u8_buffer();
                 RandomUvi::U8(n as u8, Vec::from(encode::u8(n as u8, &mut b)))
@@ -129,10 +387,18 @@ impl Arbitrary for RandomUvi {
                 let mut b = encode::u128_buffer();
                 RandomUvi::U128(n, Vec::from(encode::u128(n, &mut b)))
             }
-            _ => {
+            5 => {
                 let mut b = encode::usize_buffer();
                 RandomUvi::Usize(n as usize, Vec::from(encode::usize(n as usize, &mut b)))
             }
+            6 => {
+                let mut b = encode::i64_buffer();
+                RandomUvi::I64(n as i64, Vec::from(encode::i64(n as i64, &mut b)))
+            }
+            _ => {
+                let mut b = encode::i128_buffer();
+                RandomUvi::I128(n as i128, Vec::from(encode::i128(n as i128, &mut b)))
+            }
         }
     }
 }
@@ -0,0 +1,127 @@
+// Copyright 2026 Parity Technologies (UK) Ltd.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS
+// OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Tests against the multiformats unsigned-varint specification
+//! (https://github.com/multiformats/unsigned-varint).
+
+use unsigned_varint::{decode::{self, ErrorKind, Options}, encode};
+
+// The test vectors of the specification, extended by its maximum value.
+const VECTORS: &[(u64, &[u8])] = &[
+    (1, &[0x01]),
+    (127, &[0x7f]),
+    (128, &[0x80, 0x01]),
+    (255, &[0xff, 0x01]),
+    (300, &[0xac, 0x02]),
+    (16384, &[0x80, 0x80, 0x01]),
+    ((1 << 63) - 1, &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f])
+];
+
+// The smallest value beyond the specification limit.
+const TOO_LONG: [u8; 10] = [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01];
+
+#[test]
+fn test_vectors() {
+    for (n, bytes) in VECTORS {
+        let mut buf = encode::u64_buffer();
+        assert_eq!(*bytes, encode::u64(*n, &mut buf));
+        assert_eq!(Ok((*n, &[][..])), decode::u64_spec(bytes));
+        assert_eq!(Ok((u128::from(*n), &[][..])), decode::u128_spec(bytes));
+        assert_eq!(Ok((*n as usize, &[][..])), decode::usize_spec(bytes))
+    }
+}
+
+#[test]
+fn spec_limit() {
+    for err in [decode::u64_spec(&TOO_LONG).unwrap_err(), decode::u128_spec(&TOO_LONG).unwrap_err()] {
+        assert_eq!((ErrorKind::ExceedsSpecLimit, 8, 9), (err.kind(), err.offset(), err.examined()))
+    }
+    // Long input is also rejected by the word-at-a-time decoder.
+    assert_eq!(ErrorKind::ExceedsSpecLimit, decode::u64_spec(&[0x80; 16]).unwrap_err().kind());
+    // Beyond the specification the value is accepted by default.
+    assert_eq!(Ok((1 << 63, &[][..])), decode::u64(&TOO_LONG));
+    // A smaller limit takes precedence.
+    let options = Options::new().with_spec_limit(true).with_max_bytes(2);
+    assert_eq!(ErrorKind::Overflow, decode::u64_with(&TOO_LONG, options).unwrap_err().kind());
+    // Types which are shorter than the limit are not affected.
+    let options = Options::new().with_spec_limit(true);
+    assert_eq!(ErrorKind::Overflow, decode::u32_with(&TOO_LONG, options).unwrap_err().kind());
+    // Non-minimal encodings are not allowed by the specification.
+    assert_eq!(ErrorKind::NotMinimal, decode::u64_spec(&[0x81, 0x00]).unwrap_err().kind())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn io_spec_limit() {
+    use unsigned_varint::io::{self, ReadError};
+
+    let options = Options::new().with_spec_limit(true);
+    let mut r = std::io::Cursor::new(&TOO_LONG[..]);
+    match io::read_with::<u64, _>(&mut r, options) {
+        Err(ReadError::Decode { error, bytes }) => {
+            assert_eq!(ErrorKind::ExceedsSpecLimit, error.kind());
+            assert_eq!(&TOO_LONG[.. 9], &bytes[..])
+        }
+        other => panic!("unexpected result: {:?}", other)
+    }
+    for (n, bytes) in VECTORS {
+        assert_eq!(*n, io::read_with::<u64, _>(*bytes, options).unwrap())
+    }
+}
+
+#[cfg(feature = "futures")]
+#[test]
+fn aio_spec_limit() {
+    use unsigned_varint::{aio, io::ReadError};
+
+    futures_executor::block_on(async {
+        let options = Options::new().with_spec_limit(true);
+        let r = futures_util::io::Cursor::new(&TOO_LONG[..]);
+        match aio::read_with::<u128, _>(r, options).await {
+            Err(ReadError::Decode { error, .. }) => assert_eq!(ErrorKind::ExceedsSpecLimit, error.kind()),
+            other => panic!("unexpected result: {:?}", other)
+        }
+    })
+}
+
+#[cfg(feature = "nom")]
+#[test]
+fn nom_spec_limit() {
+    use ::nom::error::ErrorKind as NomErrorKind;
+    use unsigned_varint::nom;
+
+    let parser = nom::varint_with::<u64>(Options::new().with_spec_limit(true));
+    assert_eq!(Err(::nom::Err::Error((&TOO_LONG[..], NomErrorKind::TooLarge))), parser(&TOO_LONG));
+    assert_eq!(Ok((&[][..], 300)), parser(&[0xac, 0x02]))
+}
+
+#[cfg(feature = "codec")]
+#[test]
+fn codec_spec_limit() {
+    use bytes::BytesMut;
+    use tokio_util::codec::Decoder;
+    use unsigned_varint::codec::UviBytes;
+
+    let mut codec = UviBytes::<bytes::Bytes>::default();
+    codec.set_max_len(usize::MAX);
+    codec.set_options(Options::new().with_spec_limit(true));
+    let err = codec.decode(&mut BytesMut::from(&TOO_LONG[..])).unwrap_err();
+    let err = err.get_ref().and_then(|e| e.downcast_ref::<decode::Error>()).unwrap();
+    assert_eq!(ErrorKind::ExceedsSpecLimit, err.kind())
+}